@@ -37,6 +37,7 @@ pub fn menu_actions(
 	if keyboard_input.just_pressed(KeyCode::Escape) {
 		match current_game_state.get() {
 			GameState::Menu => game_state.set(GameState::Playing),
+			GameState::Upgrade => {}
 			_ => game_state.set(GameState::Menu),
 		}
 	}
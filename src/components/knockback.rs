@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+/// How fast a knockback slows down, per second
+const KNOCKBACK_DECAY: f32 = 8.;
+
+/// Push and stun put on an enemy by hits, read by the enemy movement
+#[derive(Component, Default)]
+pub struct Knockback {
+	pub velocity: Vec2,
+	pub stun: Timer,
+}
+
+impl Knockback {
+	pub fn push(&mut self, direction: Vec2, strength: f32) {
+		self.velocity += direction.normalize_or_zero() * strength;
+	}
+
+	/// Longer stuns replace shorter ones, they never add up
+	pub fn stun(&mut self, seconds: f32) {
+		if seconds > self.stun.remaining_secs() {
+			self.stun = Timer::from_seconds(seconds, TimerMode::Once);
+		}
+	}
+
+	pub fn is_stunned(&self) -> bool {
+		!self.stun.finished()
+	}
+
+	/// Offset to move by this frame, the push fades out over time
+	pub fn step(&mut self, delta: f32) -> Vec2 {
+		let offset = self.velocity * delta;
+		self.velocity *= (-KNOCKBACK_DECAY * delta).exp();
+		offset
+	}
+}
+
+/// Knockback and stun a bullet deals on hit
+#[derive(Component, Clone, Copy)]
+pub struct Impact {
+	pub knockback: f32,
+	pub stun: f32,
+}
@@ -1,2 +1,5 @@
 pub mod killable;
+pub mod knockback;
 pub mod playerui;
+pub mod stats;
+pub mod status;
@@ -0,0 +1,147 @@
+use bevy::ecs::component::Component;
+use bevy::utils::HashMap;
+
+use crate::constants::BASE_SPEED;
+use crate::skill::{Evolution, SkillType};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Stat {
+	MoveSpeed,
+	Damage,
+	/// Shots per second
+	AttackSpeed,
+	AttackRange,
+	BulletSpeed,
+	BulletSize,
+	MaxHp,
+	Penetration,
+	TimeDamage,
+	TimeDamageDuration,
+	PoisonDamage,
+	RicochetCount,
+	RicochetChance,
+	SpiritCount,
+	SpiritDamage,
+	MissileCount,
+	/// Beam damage per second
+	BeamDamage,
+	ExplosionRadius,
+	/// Chance from 0 to 1 for a bullet hit to crit
+	CritChance,
+	/// Damage multiplier of a crit
+	CritMultiplier,
+	/// Speed an enemy is pushed back with on hit
+	Knockback,
+	/// Seconds an enemy stops chasing after a hit
+	Stun,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModifierKind {
+	Flat,
+	Percent,
+}
+
+/// Where a modifier came from, so it can be removed again
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ModifierSource {
+	Skill(SkillType),
+	Evolution(Evolution),
+	/// Permanent upgrades bought between runs
+	Meta,
+	Item(&'static str),
+	Buff(&'static str),
+}
+
+#[derive(Clone, Copy)]
+pub struct Modifier {
+	pub kind: ModifierKind,
+	pub source: ModifierSource,
+	pub value: f32,
+}
+
+#[derive(Default)]
+pub struct StatValue {
+	pub base: f32,
+	pub modifiers: Vec<Modifier>,
+}
+
+impl StatValue {
+	pub fn new(base: f32) -> Self {
+		Self {
+			base,
+			modifiers: Vec::new(),
+		}
+	}
+
+	fn sum(&self, kind: ModifierKind) -> f32 {
+		self.modifiers.iter().filter(|m| m.kind == kind).map(|m| m.value).sum()
+	}
+
+	/// (base + flat) * (1 + percent)
+	pub fn value(&self) -> f32 {
+		(self.base + self.sum(ModifierKind::Flat)) * (1. + self.sum(ModifierKind::Percent))
+	}
+}
+
+#[derive(Component)]
+pub struct PlayerStats {
+	stats: HashMap<Stat, StatValue>,
+}
+
+impl Default for PlayerStats {
+	fn default() -> Self {
+		let stats = HashMap::from([
+			(Stat::MoveSpeed, StatValue::new(100.)),
+			(Stat::Damage, StatValue::new(2.)),
+			(Stat::AttackSpeed, StatValue::new(1.)),
+			(Stat::AttackRange, StatValue::new(1.)),
+			(Stat::BulletSpeed, StatValue::new(BASE_SPEED)),
+			(Stat::BulletSize, StatValue::new(10.)),
+			(Stat::MaxHp, StatValue::new(10.)),
+			(Stat::Penetration, StatValue::new(0.)),
+			(Stat::TimeDamage, StatValue::new(0.)),
+			(Stat::TimeDamageDuration, StatValue::new(2.)),
+			(Stat::PoisonDamage, StatValue::new(0.)),
+			(Stat::RicochetCount, StatValue::new(0.)),
+			(Stat::RicochetChance, StatValue::new(0.5)),
+			(Stat::SpiritCount, StatValue::new(0.)),
+			(Stat::SpiritDamage, StatValue::new(1.)),
+			(Stat::MissileCount, StatValue::new(0.)),
+			(Stat::BeamDamage, StatValue::new(0.)),
+			(Stat::ExplosionRadius, StatValue::new(0.)),
+			(Stat::CritChance, StatValue::new(0.05)),
+			(Stat::CritMultiplier, StatValue::new(1.5)),
+			(Stat::Knockback, StatValue::new(120.)),
+			(Stat::Stun, StatValue::new(0.)),
+		]);
+		Self { stats }
+	}
+}
+
+impl PlayerStats {
+	pub fn get(&self, stat: Stat) -> f32 {
+		self.stats.get(&stat).map_or(0., |s| s.value())
+	}
+
+	pub fn add_modifier(&mut self, stat: Stat, modifier: Modifier) {
+		self.stats.entry(stat).or_default().modifiers.push(modifier);
+	}
+
+	pub fn add_flat(&mut self, stat: Stat, source: ModifierSource, value: f32) {
+		let kind = ModifierKind::Flat;
+		self.add_modifier(stat, Modifier { kind, source, value });
+	}
+
+	pub fn add_percent(&mut self, stat: Stat, source: ModifierSource, value: f32) {
+		let kind = ModifierKind::Percent;
+		self.add_modifier(stat, Modifier { kind, source, value });
+	}
+
+	/// Drop every modifier given by the source, e.g. an expired buff
+	pub fn remove_source(&mut self, source: ModifierSource) {
+		for stat in self.stats.values_mut() {
+			stat.modifiers.retain(|m| m.source != source);
+		}
+	}
+}
@@ -0,0 +1,93 @@
+use bevy::prelude::*;
+
+use crate::ui::damage::{HINT_COLOR_BURNING, HINT_COLOR_POISON};
+
+pub const STATUS_TICK: f32 = 0.5;
+const POISON_MAX_STACKS: u32 = 5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StatusKind {
+	/// Reapplying refreshes the duration and keeps the strongest damage
+	Burning,
+	/// Reapplying adds a stack up to POISON_MAX_STACKS and refreshes the duration
+	Poison,
+}
+
+impl StatusKind {
+	pub fn hint_color(&self) -> Color {
+		match self {
+			StatusKind::Burning => HINT_COLOR_BURNING,
+			StatusKind::Poison => HINT_COLOR_POISON,
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct StatusEffect {
+	pub kind: StatusKind,
+	pub damage: i32,
+	pub stacks: u32,
+	pub duration: Timer,
+	pub tick: Timer,
+}
+
+impl StatusEffect {
+	pub fn new(kind: StatusKind, damage: i32, duration: f32) -> Self {
+		Self {
+			kind,
+			damage,
+			stacks: 1,
+			duration: Timer::from_seconds(duration, TimerMode::Once),
+			tick: Timer::from_seconds(STATUS_TICK, TimerMode::Repeating),
+		}
+	}
+
+	pub fn tick_damage(&self) -> i32 {
+		self.damage * self.stacks as i32
+	}
+}
+
+/// Status effects currently running on an enemy
+#[derive(Component, Default)]
+pub struct StatusEffects(pub Vec<StatusEffect>);
+
+impl StatusEffects {
+	pub fn apply(&mut self, effect: StatusEffect) {
+		let current = match self.0.iter_mut().find(|e| e.kind == effect.kind) {
+			Some(val) => val,
+			None => {
+				self.0.push(effect);
+				return;
+			}
+		};
+		match effect.kind {
+			StatusKind::Burning => current.damage = current.damage.max(effect.damage),
+			StatusKind::Poison => current.stacks = (current.stacks + 1).min(POISON_MAX_STACKS),
+		}
+		current.duration = effect.duration;
+	}
+}
+
+/// Status effects a bullet puts on the enemies it hits, a zero damage effect is skipped
+#[derive(Component, Clone, Copy)]
+pub struct ApplyStatus {
+	pub burning: i32,
+	pub poison: i32,
+	pub duration: f32,
+}
+
+impl ApplyStatus {
+	pub fn is_empty(&self) -> bool {
+		self.burning <= 0 && self.poison <= 0
+	}
+
+	pub fn effects(&self) -> impl Iterator<Item = StatusEffect> + '_ {
+		[
+			(StatusKind::Burning, self.burning),
+			(StatusKind::Poison, self.poison),
+		]
+		.into_iter()
+		.filter(|(_, damage)| *damage > 0)
+		.map(|(kind, damage)| StatusEffect::new(kind, damage, self.duration))
+	}
+}
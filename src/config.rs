@@ -0,0 +1,86 @@
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, AsyncReadExt, LoadContext};
+use bevy::prelude::*;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+
+use crate::loading::ConfigAssets;
+use crate::GameState;
+
+pub struct ConfigPlugin;
+
+impl Plugin for ConfigPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_asset::<ExpCurve>()
+			.init_asset_loader::<ExpCurveLoader>()
+			.init_resource::<ExpCurve>()
+			.add_systems(OnExit(GameState::Loading), apply_config);
+	}
+}
+
+/// Exp needed to finish each level
+#[derive(Asset, Resource, TypePath, Deserialize, Clone)]
+pub enum ExpCurve {
+	/// base * growth ^ (lvl - 1)
+	Formula { base: f32, growth: f32 },
+	/// The last entry repeats past the end of the table
+	Table(Vec<u32>),
+}
+
+impl Default for ExpCurve {
+	fn default() -> Self {
+		ExpCurve::Formula { base: 100., growth: 2. }
+	}
+}
+
+impl ExpCurve {
+	pub fn exp_for(&self, lvl: u32) -> u32 {
+		let exp = match self {
+			ExpCurve::Formula { base, growth } => {
+				(base * growth.powi(lvl.saturating_sub(1) as i32)) as u32
+			}
+			ExpCurve::Table(table) => {
+				let index =
+					(lvl.saturating_sub(1) as usize).min(table.len().saturating_sub(1));
+				table.get(index).copied().unwrap_or(0)
+			}
+		};
+		exp.max(1)
+	}
+}
+
+#[derive(Default)]
+pub struct ExpCurveLoader;
+
+impl AssetLoader for ExpCurveLoader {
+	type Asset = ExpCurve;
+	type Settings = ();
+	type Error = Box<dyn std::error::Error + Send + Sync>;
+
+	fn load<'a>(
+		&'a self,
+		reader: &'a mut Reader,
+		_settings: &'a (),
+		_load_context: &'a mut LoadContext,
+	) -> BoxedFuture<'a, Result<Self::Asset, Self::Error>> {
+		Box::pin(async move {
+			let mut bytes = Vec::new();
+			reader.read_to_end(&mut bytes).await?;
+			Ok(ron::de::from_bytes(&bytes)?)
+		})
+	}
+
+	fn extensions(&self) -> &[&str] {
+		&["curve.ron"]
+	}
+}
+
+fn apply_config(
+	mut commands: Commands,
+	config: Res<ConfigAssets>,
+	exp_curves: Res<Assets<ExpCurve>>,
+) {
+	if let Some(curve) = exp_curves.get(&config.exp_curve) {
+		commands.insert_resource(curve.clone());
+	}
+}
@@ -1,3 +1,14 @@
+use bevy::math::Vec2;
+
 pub const DESPAWN_BULLET_DISTANCE: f32 = 2000.0;
 pub const TIME_STEP: f32 = 1. / 60.;
 pub const BASE_SPEED: f32 = 400.;
+pub const RICOCHET_RADIUS: f32 = 300.;
+/// Player bullet reach and lifetime before attack range is applied
+pub const BULLET_RANGE: f32 = 600.;
+pub const BULLET_LIFETIME: f32 = 3.;
+/// Distance auto aim looks for targets before attack range is applied
+pub const AIM_RANGE: f32 = 500.;
+pub const CLUSTER_RADIUS: f32 = 80.;
+/// Half size of the enemy box every player hit is tested against
+pub const ENEMY_HALF_SIZE: Vec2 = Vec2::splat(25.);
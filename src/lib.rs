@@ -1,32 +1,53 @@
 mod actions;
 mod audio;
 pub mod components;
+pub mod config;
 pub mod constants;
 pub mod events;
 mod loading;
 mod menu;
+pub mod meta;
 pub mod player;
 pub mod plugins;
-mod skill;
+pub mod settings;
+pub mod skill;
 mod ui;
 
 use crate::actions::ActionsPlugin;
 use crate::audio::InternalAudioPlugin;
+use crate::config::ConfigPlugin;
 use crate::loading::LoadingPlugin;
 use crate::menu::MenuPlugin;
+use crate::meta::MetaPlugin;
 use crate::player::PlayerPlugin;
+use crate::settings::Settings;
 
 use bevy::app::App;
 #[cfg(debug_assertions)]
 use bevy::diagnostic::{FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin};
 use bevy::prelude::*;
-use constants::{BASE_SPEED, TIME_STEP};
+use plugins::beam::BeamPlugin;
+use plugins::charge::ChargeShotPlugin;
 use plugins::despawner::Despawner;
+use plugins::emitter::EmitterPlugin;
 use plugins::enemy::EnemySpawnPlugin;
+use plugins::explosive::ExplosivePlugin;
 use plugins::gameover::GameOverPlugin;
+use plugins::graze::GrazePlugin;
+use plugins::homing::HomingPlugin;
+use plugins::pool::PoolPlugin;
+use plugins::spirit::SpiritPlugin;
+use plugins::status::StatusEffectPlugin;
+use plugins::weapon::WeaponPlugin;
+use rand::{thread_rng, Rng};
+use skill::{SkillCharges, SkillManager};
+use ui::charge::PlayerChargeBar;
 use ui::damage::DamageHintPlugin;
 use ui::exp::PlayerExpBar;
+use ui::graze::PlayerGrazeBar;
 use ui::health::PlayerHealthBar;
+use ui::levelup::LevelupMenu;
+use ui::shop::ShopPlugin;
 
 #[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
 pub enum GameState {
@@ -36,6 +57,7 @@ pub enum GameState {
 	Upgrade,
 	Gameover,
 	Menu,
+	Shop,
 }
 
 #[derive(States, Default, Clone, Eq, PartialEq, Debug, Hash)]
@@ -54,7 +76,6 @@ pub struct Mouse {
 #[derive(Resource)]
 pub struct GameData {
 	pub enemy_spawn_timer: Timer,
-	pub player_shooting_timer: Timer,
 	pub player_godmod_timer: Timer,
 }
 
@@ -62,7 +83,6 @@ impl Default for GameData {
 	fn default() -> Self {
 		Self {
 			enemy_spawn_timer: Timer::from_seconds(0.5, TimerMode::Repeating),
-			player_shooting_timer: Timer::from_seconds(1.0, TimerMode::Once),
 			player_godmod_timer: Timer::from_seconds(1.0, TimerMode::Once),
 		}
 	}
@@ -85,14 +105,90 @@ pub struct Bullet {
 	damage: i32,
 }
 
+/// How many more enemies a bullet can pass through
+#[derive(Component)]
+pub struct Penetration {
+	pub remaining: u32,
+	pub hit: Vec<Entity>,
+}
+
+impl Penetration {
+	pub fn new(remaining: u32) -> Self {
+		Self {
+			remaining,
+			hit: Vec::new(),
+		}
+	}
+
+	pub fn is_hit(&self, entity: Entity) -> bool {
+		self.hit.contains(&entity)
+	}
+
+	/// Use up one pierce, returns false when the bullet is spent
+	pub fn pierce(&mut self) -> bool {
+		if self.remaining == 0 {
+			return false;
+		}
+		self.remaining -= 1;
+		true
+	}
+}
+
+/// Bullet bounces to the nearest enemy it has not hit yet
+#[derive(Component)]
+pub struct Ricochet {
+	pub charges: u32,
+	pub chance: f32,
+	pub radius: f32,
+}
+
+impl Ricochet {
+	/// Roll the chance and use up one charge on success
+	pub fn try_bounce(&mut self) -> bool {
+		if self.charges == 0 || !thread_rng().gen_bool(self.chance.clamp(0., 1.) as f64) {
+			return false;
+		}
+		self.charges -= 1;
+		true
+	}
+}
+
 #[derive(Component)]
 pub struct SpriteSize(pub Vec2);
 
+impl SpriteSize {
+	/// Half extents of the axis aligned box around the scaled and rotated sprite
+	pub fn half_extents(&self, transform: &Transform) -> Vec2 {
+		let half = self.0 * transform.scale.truncate() / 2.;
+		let (axis, angle) = transform.rotation.to_axis_angle();
+		let angle = angle * axis.z.signum();
+		let (sin, cos) = (angle.sin().abs(), angle.cos().abs());
+		Vec2::new(cos * half.x + sin * half.y, sin * half.x + cos * half.y)
+	}
+}
+
 #[derive(Component)]
 pub struct Movable {
 	pub auto_despawn: bool,
 }
 
+/// Seconds a projectile lives before it expires
+#[derive(Component)]
+pub struct Lifetime(pub Timer);
+
+impl Lifetime {
+	pub fn new(seconds: f32) -> Self {
+		Self(Timer::from_seconds(seconds, TimerMode::Once))
+	}
+}
+
+/// Distance from where the projectile was fired before it expires
+#[derive(Component)]
+pub struct MaxRange {
+	pub origin: Vec2,
+	pub range: f32,
+}
+
 #[derive(Component)]
 pub struct Velocity {
 	pub x: f32,
@@ -103,23 +199,51 @@ pub struct Velocity {
 #[derive(Component)]
 pub struct FromPlayer;
 
+#[derive(Component)]
+pub struct FromEnemy;
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
 	fn build(&self, app: &mut App) {
-		app.init_state::<GameState>().init_state::<MenuState>().add_plugins((
-			LoadingPlugin,
-			MenuPlugin,
-			GameOverPlugin,
-			ActionsPlugin,
-			InternalAudioPlugin,
-			EnemySpawnPlugin,
-			PlayerPlugin,
-			PlayerHealthBar,
-			PlayerExpBar,
-			DamageHintPlugin,
-			Despawner,
-		));
+		app.init_state::<GameState>()
+			.init_state::<MenuState>()
+			.init_resource::<SkillManager>()
+			.init_resource::<SkillCharges>()
+			.init_resource::<Settings>()
+			.add_plugins((
+				ConfigPlugin,
+				LoadingPlugin,
+				MenuPlugin,
+				GameOverPlugin,
+				ActionsPlugin,
+				InternalAudioPlugin,
+				EnemySpawnPlugin,
+				PlayerPlugin,
+				Despawner,
+				MetaPlugin,
+			))
+			.add_plugins((
+				PoolPlugin,
+				WeaponPlugin,
+				ChargeShotPlugin,
+				EmitterPlugin,
+				HomingPlugin,
+				BeamPlugin,
+				ExplosivePlugin,
+				GrazePlugin,
+				StatusEffectPlugin,
+				SpiritPlugin,
+			))
+			.add_plugins((
+				PlayerHealthBar,
+				PlayerExpBar,
+				DamageHintPlugin,
+				LevelupMenu,
+				ShopPlugin,
+				PlayerChargeBar,
+				PlayerGrazeBar,
+			));
 
 		#[cfg(debug_assertions)]
 		{
@@ -128,10 +252,13 @@ impl Plugin for GamePlugin {
 	}
 }
 
-fn movable_system(mut query: Query<(&Velocity, &mut Transform), With<Bullet>>) {
+fn movable_system(
+	time: Res<Time>,
+	mut query: Query<(&Velocity, &mut Transform), With<Bullet>>,
+) {
 	for (velocity, mut transform) in query.iter_mut() {
 		let translation = &mut transform.translation;
-		translation.x += velocity.x * TIME_STEP * BASE_SPEED;
-		translation.y += velocity.y * TIME_STEP * BASE_SPEED;
+		translation.x += velocity.x * velocity.speed * time.delta_seconds();
+		translation.y += velocity.y * velocity.speed * time.delta_seconds();
 	}
 }
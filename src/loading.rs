@@ -1,5 +1,8 @@
+use crate::config::ExpCurve;
 use crate::GameState;
+use bevy::math::primitives::Circle;
 use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
 use bevy_asset_loader::prelude::*;
 use bevy_kira_audio::AudioSource;
 
@@ -12,7 +15,9 @@ impl Plugin for LoadingPlugin {
 				.continue_to_state(GameState::Menu)
 				.load_collection::<FontAssets>()
 				.load_collection::<AudioAssets>()
-				.load_collection::<TextureAssets>(),
+				.load_collection::<TextureAssets>()
+				.load_collection::<ConfigAssets>()
+				.init_resource::<BulletAssets>(),
 		);
 	}
 }
@@ -43,4 +48,67 @@ pub struct TextureAssets {
 	pub enemy: Handle<Image>,
 	#[asset(path = "textures/heart.png")]
 	pub heart: Handle<Image>,
+	#[asset(path = "textures/laser_a.png")]
+	pub laser_a: Handle<Image>,
+	#[asset(path = "textures/laser_b.png")]
+	pub laser_b: Handle<Image>,
+	#[asset(path = "textures/explo_sheet.png")]
+	pub explosion: Handle<Image>,
+	#[asset(texture_atlas_layout(
+		tile_size_x = 64.,
+		tile_size_y = 64.,
+		columns = 4,
+		rows = 4
+	))]
+	pub explosion_layout: Handle<TextureAtlasLayout>,
+}
+
+#[derive(AssetCollection, Resource)]
+pub struct ConfigAssets {
+	#[asset(path = "config/exp.curve.ron")]
+	pub exp_curve: Handle<ExpCurve>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BulletKind {
+	Player,
+	Enemy,
+}
+
+/// Mesh and materials shared by every bullet, built once while loading
+#[derive(Resource)]
+pub struct BulletAssets {
+	pub circle: Handle<Mesh>,
+	pub player: Handle<ColorMaterial>,
+	pub enemy: Handle<ColorMaterial>,
+}
+
+impl FromWorld for BulletAssets {
+	fn from_world(world: &mut World) -> Self {
+		let circle = world.resource_mut::<Assets<Mesh>>().add(Circle::default());
+		let mut materials = world.resource_mut::<Assets<ColorMaterial>>();
+		Self {
+			circle,
+			player: materials.add(ColorMaterial::from(Color::RED)),
+			enemy: materials.add(ColorMaterial::from(Color::ORANGE)),
+		}
+	}
+}
+
+impl BulletAssets {
+	pub fn material(&self, kind: BulletKind) -> Handle<ColorMaterial> {
+		match kind {
+			BulletKind::Player => self.player.clone(),
+			BulletKind::Enemy => self.enemy.clone(),
+		}
+	}
+
+	/// Bullet mesh bundle of the kind, the transform is left to the caller
+	pub fn bundle(&self, kind: BulletKind) -> MaterialMesh2dBundle<ColorMaterial> {
+		MaterialMesh2dBundle {
+			mesh: self.circle.clone().into(),
+			material: self.material(kind),
+			..default()
+		}
+	}
 }
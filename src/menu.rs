@@ -1,4 +1,5 @@
 use crate::loading::FontAssets;
+use crate::settings::Settings;
 use crate::GameState;
 use bevy::app::AppExit;
 use bevy::prelude::*;
@@ -12,7 +13,8 @@ impl Plugin for MenuPlugin {
 			.add_systems(
 				Update,
 				(
-					handle_hover_buttons.run_if(in_state(GameState::Menu)),
+					handle_hover_buttons
+						.run_if(in_state(GameState::Menu).or_else(in_state(GameState::Shop))),
 					menu_action.run_if(in_state(GameState::Menu)),
 				),
 			)
@@ -21,19 +23,25 @@ impl Plugin for MenuPlugin {
 }
 
 #[derive(Resource)]
-struct ButtonColors {
-	clicked: Color,
-	normal: Color,
-	hovered: Color,
-	fade: Color,
+pub(crate) struct ButtonColors {
+	pub clicked: Color,
+	pub normal: Color,
+	pub hovered: Color,
+	pub fade: Color,
 }
 
 #[derive(Component)]
 enum MenuButtonAction {
 	Play,
+	Shop,
+	Aim,
 	Quit,
 }
 
+/// Text of the aim mode button
+#[derive(Component)]
+struct AimLabel;
+
 impl Default for ButtonColors {
 	fn default() -> Self {
 		ButtonColors {
@@ -49,6 +57,7 @@ fn setup_menu(
 	mut commands: Commands,
 	font_assets: Res<FontAssets>,
 	button_colors: Res<ButtonColors>,
+	settings: Res<Settings>,
 ) {
 	commands
 		.spawn(NodeBundle {
@@ -90,6 +99,57 @@ fn setup_menu(
 						},
 					));
 				});
+			parent
+				.spawn((
+					ButtonBundle {
+						style: Style {
+							width: Val::Px(200.0),
+							height: Val::Px(50.0),
+							justify_content: JustifyContent::Center,
+							align_items: AlignItems::Center,
+							..Default::default()
+						},
+						..Default::default()
+					},
+					MenuButtonAction::Shop,
+				))
+				.with_children(|parent| {
+					parent.spawn(TextBundle::from_section(
+						"Upgrades",
+						TextStyle {
+							font: font_assets.fira_sans.clone(),
+							font_size: 20.0,
+							color: Color::rgb(0.8, 0.8, 0.8),
+						},
+					));
+				});
+			parent
+				.spawn((
+					ButtonBundle {
+						style: Style {
+							width: Val::Px(200.0),
+							height: Val::Px(50.0),
+							justify_content: JustifyContent::Center,
+							align_items: AlignItems::Center,
+							..Default::default()
+						},
+						..Default::default()
+					},
+					MenuButtonAction::Aim,
+				))
+				.with_children(|parent| {
+					parent.spawn((
+						TextBundle::from_section(
+							format!("Aim: {}", settings.aim.name()),
+							TextStyle {
+								font: font_assets.fira_sans.clone(),
+								font_size: 20.0,
+								color: Color::rgb(0.8, 0.8, 0.8),
+							},
+						),
+						AimLabel,
+					));
+				});
 			parent
 				.spawn((
 					ButtonBundle {
@@ -146,6 +206,8 @@ fn menu_action(
 	>,
 	mut app_exit_events: EventWriter<AppExit>,
 	mut game_state: ResMut<NextState<GameState>>,
+	mut settings: ResMut<Settings>,
+	mut label_query: Query<&mut Text, With<AimLabel>>,
 ) {
 	for (interaction, menu_button_action) in &interaction_query {
 		if *interaction == Interaction::Pressed {
@@ -156,6 +218,15 @@ fn menu_action(
 				MenuButtonAction::Play => {
 					game_state.set(GameState::Playing);
 				}
+				MenuButtonAction::Shop => {
+					game_state.set(GameState::Shop);
+				}
+				MenuButtonAction::Aim => {
+					settings.next_aim();
+					for mut text in &mut label_query {
+						text.sections[0].value = format!("Aim: {}", settings.aim.name());
+					}
+				}
 			}
 		}
 	}
@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+	components::stats::{ModifierSource, PlayerStats, Stat},
+	player::Player,
+	GameState,
+};
+
+#[cfg(not(target_arch = "wasm32"))]
+const SAVE_PATH: &str = "save/meta.ron";
+const MAX_UPGRADE_LEVEL: u32 = 5;
+
+pub struct MetaPlugin;
+
+impl Plugin for MetaPlugin {
+	fn build(&self, app: &mut App) {
+		app.insert_resource(MetaProgress::load())
+			.init_resource::<RunStats>()
+			.add_systems(OnEnter(GameState::Gameover), award_run);
+	}
+}
+
+/// Permanent upgrades bought between runs
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize, Deserialize)]
+pub enum MetaUpgrade {
+	StartingHp,
+	BaseDamage,
+	MoveSpeed,
+	AttackSpeed,
+}
+
+impl MetaUpgrade {
+	pub const ALL: [MetaUpgrade; 4] = [
+		MetaUpgrade::StartingHp,
+		MetaUpgrade::BaseDamage,
+		MetaUpgrade::MoveSpeed,
+		MetaUpgrade::AttackSpeed,
+	];
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			MetaUpgrade::StartingHp => "Starting HP",
+			MetaUpgrade::BaseDamage => "Base damage",
+			MetaUpgrade::MoveSpeed => "Move speed",
+			MetaUpgrade::AttackSpeed => "Attack speed",
+		}
+	}
+
+	pub fn description(&self) -> &'static str {
+		match self {
+			MetaUpgrade::StartingHp => "+2 max HP",
+			MetaUpgrade::BaseDamage => "+1 bullet damage",
+			MetaUpgrade::MoveSpeed => "+5% move speed",
+			MetaUpgrade::AttackSpeed => "+5% attack speed",
+		}
+	}
+
+	/// Price of the next level
+	pub fn cost(&self, level: u32) -> u32 {
+		let base = match self {
+			MetaUpgrade::StartingHp => 20,
+			MetaUpgrade::BaseDamage => 40,
+			MetaUpgrade::MoveSpeed => 25,
+			MetaUpgrade::AttackSpeed => 30,
+		};
+		base * (level + 1)
+	}
+
+	fn apply(&self, level: u32, stats: &mut PlayerStats) {
+		let source = ModifierSource::Meta;
+		let level = level as f32;
+		match self {
+			MetaUpgrade::StartingHp => stats.add_flat(Stat::MaxHp, source, 2. * level),
+			MetaUpgrade::BaseDamage => stats.add_flat(Stat::Damage, source, level),
+			MetaUpgrade::MoveSpeed => stats.add_percent(Stat::MoveSpeed, source, 0.05 * level),
+			MetaUpgrade::AttackSpeed => {
+				stats.add_percent(Stat::AttackSpeed, source, 0.05 * level)
+			}
+		}
+	}
+}
+
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct MetaProgress {
+	pub currency: u32,
+	pub upgrades: BTreeMap<MetaUpgrade, u32>,
+}
+
+impl MetaProgress {
+	pub fn level(&self, upgrade: MetaUpgrade) -> u32 {
+		self.upgrades.get(&upgrade).copied().unwrap_or(0)
+	}
+
+	pub fn is_maxed(&self, upgrade: MetaUpgrade) -> bool {
+		self.level(upgrade) >= MAX_UPGRADE_LEVEL
+	}
+
+	/// Spend currency on the next level, returns false if it is maxed or too expensive
+	pub fn buy(&mut self, upgrade: MetaUpgrade) -> bool {
+		let level = self.level(upgrade);
+		let cost = upgrade.cost(level);
+		if self.is_maxed(upgrade) || self.currency < cost {
+			return false;
+		}
+		self.currency -= cost;
+		self.upgrades.insert(upgrade, level + 1);
+		true
+	}
+
+	/// Add the bought upgrades to a fresh player
+	pub fn apply(&self, stats: &mut PlayerStats) {
+		for (upgrade, level) in self.upgrades.iter() {
+			upgrade.apply(*level, stats);
+		}
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn load() -> Self {
+		std::fs::read_to_string(SAVE_PATH)
+			.ok()
+			.and_then(|data| ron::from_str(&data).ok())
+			.unwrap_or_default()
+	}
+
+	#[cfg(target_arch = "wasm32")]
+	pub fn load() -> Self {
+		Self::default()
+	}
+
+	#[cfg(not(target_arch = "wasm32"))]
+	pub fn save(&self) {
+		let path = std::path::Path::new(SAVE_PATH);
+		let result = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+			.map_err(std::io::Error::other)
+			.and_then(|data| {
+				if let Some(dir) = path.parent() {
+					std::fs::create_dir_all(dir)?;
+				}
+				std::fs::write(path, data)
+			});
+		if let Err(err) = result {
+			error!("Failed to save meta progress: {err}");
+		}
+	}
+
+	#[cfg(target_arch = "wasm32")]
+	pub fn save(&self) {}
+}
+
+/// Counters of the current run
+#[derive(Resource, Default)]
+pub struct RunStats {
+	pub kills: u32,
+	pub grazes: u32,
+}
+
+/// Grazes needed for one coin
+const GRAZES_PER_COIN: u32 = 2;
+
+pub fn run_reward(lvl: u32, stats: &RunStats) -> u32 {
+	lvl * 10 + stats.kills + stats.grazes / GRAZES_PER_COIN
+}
+
+fn award_run(query: Query<&Player>, run_stats: Res<RunStats>, mut meta: ResMut<MetaProgress>) {
+	if let Ok(player) = query.get_single() {
+		meta.currency += run_reward(player.lvl, &run_stats);
+		meta.save();
+	}
+}
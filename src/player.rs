@@ -1,16 +1,32 @@
 use crate::actions::Actions;
 use crate::components::killable::Killable;
+use crate::components::knockback::{Impact, Knockback};
+use crate::components::stats::{PlayerStats, Stat};
+use crate::components::status::ApplyStatus;
+use crate::config::ExpCurve;
+use crate::constants::ENEMY_HALF_SIZE;
 use crate::loading::TextureAssets;
-use crate::ui::damage::EventDamageHintSpawn;
+use crate::meta::{MetaProgress, RunStats};
+use crate::plugins::charge::ChargeShot;
+use crate::plugins::despawner::ExpireSet;
+use crate::plugins::explosive::{EventExplosion, Explosive};
+use crate::plugins::graze::GrazeMeter;
+use crate::plugins::pool::{BulletParts, PoolCommandsExt};
+use crate::plugins::status::EventApplyStatus;
+use crate::plugins::weapon::{nearest_enemy, MainWeapon, Weapon};
+use crate::settings::Settings;
+use crate::skill::{SkillCharges, SkillManager};
+use crate::ui::damage::{EventDamageHintSpawn, HitKind};
 use crate::{
-	movable_system, Bullet, Enemy, FromPlayer, GameData, GameState, MainCamera, Mouse,
-	Movable, SceneObject, SpriteSize, Velocity,
+	movable_system, Bullet, Enemy, FromEnemy, FromPlayer, GameData, GameState, MainCamera,
+	MaxRange, Mouse, Penetration, Ricochet, SceneObject, SpriteSize, Velocity,
 };
+use bevy::ecs::system::SystemParam;
 use bevy::math::bounding::{Aabb2d, IntersectsVolume};
-use bevy::math::primitives::Circle;
 use bevy::prelude::*;
 use bevy::sprite::MaterialMesh2dBundle;
 use bevy_hanabi::prelude::*;
+use rand::{thread_rng, Rng};
 
 pub struct PlayerPlugin;
 
@@ -21,11 +37,11 @@ pub struct Player {
 	pub lvl: u32,
 }
 
-impl Default for Player {
-	fn default() -> Self {
+impl Player {
+	pub fn new(curve: &ExpCurve) -> Self {
 		Self {
 			exp: 0,
-			next_lvl_exp: get_lvl_exp(1),
+			next_lvl_exp: curve.exp_for(1),
 			lvl: 1,
 		}
 	}
@@ -50,10 +66,17 @@ pub struct PlayerGetExpEvent {
 	pub exp: u32,
 }
 
+/// Sent once for every level gained
+#[derive(Event)]
+pub struct PlayerLeveledUp {
+	pub lvl: u32,
+}
+
 impl Plugin for PlayerPlugin {
 	fn build(&self, app: &mut App) {
 		app.add_event::<PlayerGetDamageEvent>()
 			.add_event::<PlayerGetExpEvent>()
+			.add_event::<PlayerLeveledUp>()
 			.add_systems(OnEnter(GameState::Playing), spawn_player)
 			.add_systems(
 				Update,
@@ -62,12 +85,17 @@ impl Plugin for PlayerPlugin {
 					camera_move.run_if(in_state(GameState::Playing)),
 					dodge_roll_system.run_if(in_state(GameState::Playing)),
 					turn_player.run_if(in_state(GameState::Playing)),
-					player_fire_system.run_if(in_state(GameState::Playing)),
 					movable_system.run_if(in_state(GameState::Playing)),
 					player_damage_system.run_if(in_state(GameState::Playing)),
-					player_bullet_hit_system.run_if(in_state(GameState::Playing)),
+					enemy_bullet_hit_system.run_if(in_state(GameState::Playing)),
+					// Bullets released on hit are gone before expiry is checked,
+					// so a grenade hitting on its last frame does not explode twice
+					player_bullet_hit_system
+						.before(ExpireSet::Detect)
+						.run_if(in_state(GameState::Playing)),
 					get_player_damage_event.run_if(in_state(GameState::Playing)),
 					get_player_exp_event.run_if(in_state(GameState::Playing)),
+					update_player_max_hp.run_if(in_state(GameState::Playing)),
 				),
 			);
 	}
@@ -117,27 +145,6 @@ fn dodge_roll_system(
 	}
 }
 
-fn get_lvl_exp(lvl: u32) -> u32 {
-	match lvl {
-		1 => 100,
-		2 => 200,
-		3 => 400,
-		4 => 800,
-		5 => 1600,
-		6 => 3200,
-		7 => 6400,
-		8 => 12800,
-		9 => 25600,
-		10 => 51200,
-		11 => 102400,
-		12 => 204800,
-		13 => 409600,
-		14 => 819200,
-		15 => 819200 * 2,
-		_ => 100,
-	}
-}
-
 fn get_player_damage_event(
 	mut ev_pdamage: EventReader<PlayerGetDamageEvent>,
 	mut query: Query<&mut Killable, With<Player>>,
@@ -156,6 +163,8 @@ fn get_player_damage_event(
 fn get_player_exp_event(
 	mut event: EventReader<PlayerGetExpEvent>,
 	mut query: Query<&mut Player>,
+	mut level_event: EventWriter<PlayerLeveledUp>,
+	curve: Res<ExpCurve>,
 ) {
 	let mut player = match query.get_single_mut() {
 		Ok(val) => val,
@@ -164,11 +173,59 @@ fn get_player_exp_event(
 	for ev in event.read() {
 		player.exp += ev.exp;
 
-		if player.exp >= player.next_lvl_exp {
+		while player.exp >= player.next_lvl_exp {
 			player.lvl += 1;
 			player.exp -= player.next_lvl_exp;
-			player.next_lvl_exp = get_lvl_exp(player.lvl);
+			player.next_lvl_exp = curve.exp_for(player.lvl);
+			level_event.send(PlayerLeveledUp { lvl: player.lvl });
+		}
+	}
+}
+
+fn update_player_max_hp(
+	mut query: Query<(&Transform, &PlayerStats, &mut Killable), Changed<PlayerStats>>,
+	mut damage_hint_event: EventWriter<EventDamageHintSpawn>,
+) {
+	for (transform, stats, mut killable) in query.iter_mut() {
+		let hp_max = stats.get(Stat::MaxHp) as i32;
+		let heal = hp_max - killable.hp_max;
+		killable.hp += heal;
+		killable.hp_max = hp_max;
+		if heal > 0 {
+			damage_hint_event.send(EventDamageHintSpawn {
+				damage: heal as u32,
+				position: transform.translation.truncate(),
+				kind: HitKind::Heal,
+			});
+		}
+	}
+}
+
+/// Run-wide state, reset when the player is spawned for a new run
+#[derive(SystemParam)]
+struct NewRun<'w, 's> {
+	query: Query<'w, 's, Entity, With<Player>>,
+	skills: ResMut<'w, SkillManager>,
+	charges: ResMut<'w, SkillCharges>,
+	run_stats: ResMut<'w, RunStats>,
+	meta: Res<'w, MetaProgress>,
+	curve: Res<'w, ExpCurve>,
+	settings: Res<'w, Settings>,
+}
+
+impl NewRun<'_, '_> {
+	/// Clear the last run, returns the stats the player starts with or
+	/// nothing when the player is still alive and play just resumes
+	fn start(&mut self) -> Option<PlayerStats> {
+		if !self.query.is_empty() {
+			return None;
 		}
+		self.skills.reset();
+		*self.charges = SkillCharges::default();
+		*self.run_stats = RunStats::default();
+		let mut stats = PlayerStats::default();
+		self.meta.apply(&mut stats);
+		Some(stats)
 	}
 }
 
@@ -176,14 +233,16 @@ fn spawn_player(
 	mut commands: Commands,
 	textures: Res<TextureAssets>,
 	images: Res<Assets<Image>>,
-	query: Query<Entity, With<Player>>,
+	mut run: NewRun,
 	mut effects: ResMut<Assets<EffectAsset>>,
 	mut meshes: ResMut<Assets<Mesh>>,
 	mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
-	if query.iter().count() > 0 {
-		return;
-	}
+	let stats = match run.start() {
+		Some(val) => val,
+		None => return,
+	};
+	let hp_max = stats.get(Stat::MaxHp) as i32;
 	let image = images.get(&textures.player).unwrap();
 	let player_sprite = SpriteBundle {
 		texture: textures.player.clone(),
@@ -197,14 +256,22 @@ fn spawn_player(
 	commands
 		.spawn(player_sprite)
 		.insert(Killable {
-			hp: 10,
+			hp: hp_max,
 			god_mode: false,
-			hp_max: 10,
+			hp_max,
 		})
+		.insert(stats)
 		.insert(SpriteSize(image.size_f32()))
 		.insert(PlayerMove)
 		.insert(SceneObject)
-		.insert(Player::default());
+		.insert(Player::new(&run.curve))
+		.insert(ChargeShot::default())
+		.insert(GrazeMeter::default())
+		.with_children(|parent| {
+			parent
+				.spawn(Weapon::blaster().with_targeting(run.settings.aim))
+				.insert(MainWeapon);
+		});
 
 	commands
 		.spawn(MaterialMesh2dBundle {
@@ -296,86 +363,167 @@ fn player_damage_system(
 	}
 }
 
-fn player_bullet_hit_system(
+pub fn enemy_bullet_hit_system(
 	mut commands: Commands,
-	mut enemy_query: Query<(&Transform, &mut Killable), With<Enemy>>,
-	mut bullet_query: Query<(Entity, &Transform, &Bullet), With<Bullet>>,
-	mut damage_hint_event: EventWriter<EventDamageHintSpawn>,
+	bullet_query: Query<(Entity, &Transform, &Bullet), With<FromEnemy>>,
+	player_query: Query<(&Transform, &Killable, &SpriteSize), With<Player>>,
+	mut event: EventWriter<PlayerGetDamageEvent>,
+	mut game_data: ResMut<GameData>,
 ) {
-	for (bullet_entity, bullet_transform, bullet) in bullet_query.iter_mut() {
-		for (enemy_transform, mut killable) in enemy_query.iter_mut() {
-			let collision = Aabb2d::new(
-				bullet_transform.translation.truncate(),
-				bullet_transform.scale.truncate(),
-			)
-			.intersects(&Aabb2d::new(
-				enemy_transform.translation.truncate(),
-				Vec2 { x: 25., y: 25. },
-			));
-
-			if collision {
-				killable.hp -= bullet.damage;
-				damage_hint_event.send(EventDamageHintSpawn {
-					damage: bullet.damage as u32,
-					position: enemy_transform.translation.truncate(),
-				});
-				commands.entity(bullet_entity).despawn();
-			}
+	let (player_transform, killable, sprite) = match player_query.get_single() {
+		Ok(val) => val,
+		Err(_) => return,
+	};
+	let player_aabb = Aabb2d::new(
+		player_transform.translation.truncate(),
+		sprite.0 * player_transform.scale.truncate() / 2.,
+	);
+	for (bullet_entity, bullet_transform, bullet) in bullet_query.iter() {
+		if killable.god_mode || !game_data.player_godmod_timer.finished() {
+			return;
+		}
+		let collision = Aabb2d::new(
+			bullet_transform.translation.truncate(),
+			bullet_transform.scale.truncate() / 2.,
+		)
+		.intersects(&player_aabb);
+
+		if collision {
+			game_data.player_godmod_timer.reset();
+			event.send(PlayerGetDamageEvent { damage: bullet.damage });
+			commands.release::<BulletParts>(bullet_entity);
 		}
 	}
 }
 
-fn player_fire_system(
+/// Player bullet with everything a hit can change or read
+type PlayerBulletHit = (
+	Entity,
+	&'static Transform,
+	&'static Bullet,
+	&'static mut Penetration,
+	&'static mut Velocity,
+	Option<&'static mut Ricochet>,
+	Option<&'static ApplyStatus>,
+	Option<&'static SpriteSize>,
+	Option<&'static Explosive>,
+	Option<&'static Impact>,
+	Option<&'static mut MaxRange>,
+);
+
+fn player_bullet_hit_system(
 	mut commands: Commands,
-	kb: Res<ButtonInput<KeyCode>>,
-	buttons: Res<ButtonInput<MouseButton>>,
-	query: Query<&Transform, (With<Player>, With<Killable>)>,
-	mut meshes: ResMut<Assets<Mesh>>,
-	mut materials: ResMut<Assets<ColorMaterial>>,
-	mouse: Res<Mouse>,
-	mut game_data: ResMut<GameData>,
-	time: Res<Time>,
+	mut enemy_query: Query<
+		(Entity, &Transform, &mut Killable, Option<&mut Knockback>),
+		With<Enemy>,
+	>,
+	mut bullet_query: Query<PlayerBulletHit, With<FromPlayer>>,
+	mut damage_hint_event: EventWriter<EventDamageHintSpawn>,
+	mut status_event: EventWriter<EventApplyStatus>,
+	mut explosion_event: EventWriter<EventExplosion>,
+	player_query: Query<&PlayerStats, With<Player>>,
 ) {
-	game_data.player_shooting_timer.tick(time.delta());
-	if let Ok(player_tf) = query.get_single() {
-		if game_data.player_shooting_timer.finished()
-			&& (kb.pressed(KeyCode::Space) || buttons.pressed(MouseButton::Left))
+	let (crit_chance, crit_multiplier) = match player_query.get_single() {
+		Ok(stats) => (
+			stats.get(Stat::CritChance).clamp(0., 1.) as f64,
+			stats.get(Stat::CritMultiplier),
+		),
+		Err(_) => return,
+	};
+	let enemies: Vec<(Entity, Vec2)> = enemy_query
+		.iter()
+		.filter(|(_, _, killable, _)| killable.hp > 0)
+		.map(|(entity, transform, _, _)| (entity, transform.translation.truncate()))
+		.collect();
+	for (
+		bullet_entity,
+		bullet_transform,
+		bullet,
+		mut penetration,
+		mut velocity,
+		mut ricochet,
+		status,
+		sprite,
+		explosive,
+		impact,
+		mut max_range,
+	) in bullet_query.iter_mut()
+	{
+		// Mesh bullets are sized by their scale, sprite bullets by the image
+		let bullet_half = match sprite {
+			Some(sprite) => sprite.half_extents(bullet_transform),
+			None => bullet_transform.scale.truncate(),
+		};
+		for (enemy_entity, enemy_transform, mut killable, mut knockback) in
+			enemy_query.iter_mut()
 		{
-			game_data.player_shooting_timer.reset();
-			let (x, y) = (player_tf.translation.x, player_tf.translation.y);
-			let p_transform = Vec2 { x, y };
-			let target = Vec2 {
-				x: mouse.position.x,
-				y: mouse.position.y,
-			};
-			let diff = target - p_transform;
-			let x_offset = 0.;
-
-			let mut spawn_laser = |x_offset: f32| {
-				commands
-					.spawn(MaterialMesh2dBundle {
-						mesh: meshes.add(Circle::default()).into(),
-						material: materials.add(ColorMaterial::from(Color::RED)),
-						transform: Transform::from_translation(Vec3::new(
-							x + x_offset,
-							y + 0.,
-							0.,
-						))
-						.with_scale(Vec3 { x: 10.0, y: 10.0, z: 0.0 }),
-						..default()
-					})
-					.insert(Bullet { damage: 2 })
-					.insert(SceneObject)
-					.insert(FromPlayer)
-					.insert(Movable { auto_despawn: true })
-					.insert(Velocity {
-						x: diff.normalize().x,
-						y: diff.normalize().y,
-						speed: 50.0,
-					});
-			};
+			if killable.hp <= 0 || penetration.is_hit(enemy_entity) {
+				continue;
+			}
+			let enemy_aabb =
+				Aabb2d::new(enemy_transform.translation.truncate(), ENEMY_HALF_SIZE);
+			let collision = Aabb2d::new(bullet_transform.translation.truncate(), bullet_half)
+				.intersects(&enemy_aabb);
 
-			spawn_laser(x_offset);
+			if collision {
+				// The blast deals the damage, including to the enemy that was hit
+				if let Some(explosive) = explosive {
+					let position = bullet_transform.translation.truncate();
+					explosion_event.send(explosive.explode(position));
+					commands.release::<BulletParts>(bullet_entity);
+					break;
+				}
+				let (damage, kind) = if thread_rng().gen_bool(crit_chance) {
+					let damage = bullet.damage as f32 * crit_multiplier;
+					(damage.round() as i32, HitKind::Crit)
+				} else {
+					(bullet.damage, HitKind::Normal)
+				};
+				killable.hit(damage);
+				damage_hint_event.send(EventDamageHintSpawn {
+					damage: damage as u32,
+					position: enemy_transform.translation.truncate(),
+					kind,
+				});
+				if let (Some(impact), Some(knockback)) = (impact, knockback.as_mut()) {
+					knockback.push(Vec2::new(velocity.x, velocity.y), impact.knockback);
+					knockback.stun(impact.stun);
+				}
+				for effect in status.iter().flat_map(|status| status.effects()) {
+					status_event.send(EventApplyStatus {
+						target: enemy_entity,
+						effect,
+					});
+				}
+				penetration.hit.push(enemy_entity);
+
+				if let Some(ricochet) = ricochet.as_mut() {
+					let position = bullet_transform.translation.truncate();
+					// Enemies the bullet already went through are not bounced to again
+					let candidates = enemies
+						.iter()
+						.filter(|(entity, _)| !penetration.is_hit(*entity))
+						.map(|(_, enemy)| *enemy)
+						.filter(|enemy| enemy.distance(position) <= ricochet.radius);
+					let target = nearest_enemy(candidates, position);
+					if let Some(target) = target {
+						if ricochet.try_bounce() {
+							let direction = (target - position).normalize_or_zero();
+							velocity.x = direction.x;
+							velocity.y = direction.y;
+							// Range counts again from the bounce
+							if let Some(max_range) = max_range.as_mut() {
+								max_range.origin = position;
+							}
+							break;
+						}
+					}
+				}
+				if !penetration.pierce() {
+					commands.release::<BulletParts>(bullet_entity);
+					break;
+				}
+			}
 		}
 	}
 }
@@ -398,13 +546,17 @@ fn camera_move(
 fn move_player(
 	time: Res<Time>,
 	actions: Res<Actions>,
+	stats_query: Query<&PlayerStats, With<Player>>,
 	mut player_query: Query<&mut Transform, With<PlayerMove>>,
 	//mut camera_query: Query<&mut Transform, (With<MainCamera>, Without<PlayerMove>)>,
 ) {
 	if actions.player_movement.is_none() {
 		return;
 	}
-	let speed = 100.;
+	let speed = match stats_query.get_single() {
+		Ok(stats) => stats.get(Stat::MoveSpeed),
+		Err(_) => return,
+	};
 	let movement = Vec3::new(
 		actions.player_movement.unwrap().x * speed * time.delta_seconds(),
 		actions.player_movement.unwrap().y * speed * time.delta_seconds(),
@@ -0,0 +1,197 @@
+use bevy::math::bounding::{Aabb2d, RayCast2d};
+use bevy::math::primitives::Direction2d;
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::utils::HashMap;
+
+use crate::{
+	components::{
+		killable::Killable,
+		stats::{PlayerStats, Stat},
+	},
+	constants::{AIM_RANGE, ENEMY_HALF_SIZE},
+	loading::TextureAssets,
+	player::Player,
+	plugins::weapon::{aim_direction, FireInput, Targeting},
+	settings::Settings,
+	ui::damage::{EventDamageHintSpawn, HitKind},
+	Enemy, GameState, SceneObject,
+};
+
+/// Longest beam before attack range is applied
+const BEAM_LENGTH: f32 = 350.;
+const BEAM_WIDTH: f32 = 12.;
+/// Seconds between two damage hints of the beam, damage in between is summed up
+const BEAM_HINT_INTERVAL: f32 = 0.3;
+
+pub struct BeamPlugin;
+
+impl Plugin for BeamPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			Update,
+			(
+				beam_count_system.run_if(in_state(GameState::Playing)),
+				beam_system.run_if(in_state(GameState::Playing)),
+			),
+		);
+	}
+}
+
+/// Continuous laser from the player, hurts every enemy it crosses each frame
+#[derive(Component)]
+pub struct Beam {
+	/// Damage not yet taken from hp, hp only goes down in whole points
+	pending: HashMap<Entity, f32>,
+	/// Damage dealt since the last hint, with the enemy position
+	unshown: HashMap<Entity, (u32, Vec2)>,
+	hint_timer: Timer,
+}
+
+impl Default for Beam {
+	fn default() -> Self {
+		Self {
+			pending: HashMap::new(),
+			unshown: HashMap::new(),
+			hint_timer: Timer::from_seconds(BEAM_HINT_INTERVAL, TimerMode::Repeating),
+		}
+	}
+}
+
+/// Keeps the beam apart from the player and enemy transforms
+type BeamFilter = (Without<Player>, Without<Enemy>);
+
+/// Enemies read to aim the beam, then hurt by it
+type EnemyQueries<'w, 's> = ParamSet<
+	'w,
+	's,
+	(
+		Query<'static, 'static, (&'static Transform, &'static Killable), With<Enemy>>,
+		Query<
+			'static,
+			'static,
+			(Entity, &'static Transform, &'static mut Killable),
+			With<Enemy>,
+		>,
+	),
+>;
+
+/// Spawns the beam once the beam skill is taken
+fn beam_count_system(
+	mut commands: Commands,
+	textures: Res<TextureAssets>,
+	player_query: Query<&PlayerStats, With<Player>>,
+	beam_query: Query<Entity, With<Beam>>,
+) {
+	let stats = match player_query.get_single() {
+		Ok(val) => val,
+		Err(_) => return,
+	};
+	let active = stats.get(Stat::BeamDamage) > 0.;
+	match beam_query.get_single() {
+		Ok(entity) if !active => commands.entity(entity).despawn_recursive(),
+		Err(_) if active => {
+			commands
+				.spawn(SpriteBundle {
+					texture: textures.laser_a.clone(),
+					sprite: Sprite {
+						anchor: Anchor::BottomCenter,
+						..default()
+					},
+					visibility: Visibility::Hidden,
+					..default()
+				})
+				.insert(Beam::default())
+				.insert(SceneObject);
+		}
+		_ => {}
+	}
+}
+
+fn beam_system(
+	time: Res<Time>,
+	input: FireInput,
+	settings: Res<Settings>,
+	player_query: Query<(&Transform, &PlayerStats), With<Player>>,
+	mut beam_query: Query<
+		(&mut Beam, &mut Transform, &mut Sprite, &mut Visibility),
+		BeamFilter,
+	>,
+	mut enemy_queries: EnemyQueries,
+	mut damage_hint_event: EventWriter<EventDamageHintSpawn>,
+) {
+	let (player_tf, stats) = match player_query.get_single() {
+		Ok(val) => val,
+		Err(_) => return,
+	};
+	let (mut beam, mut transform, mut sprite, mut visibility) =
+		match beam_query.get_single_mut() {
+			Ok(val) => val,
+			Err(_) => return,
+		};
+
+	beam.hint_timer.tick(time.delta());
+	if beam.hint_timer.just_finished() {
+		for (damage, position) in beam.unshown.drain().map(|(_, val)| val) {
+			damage_hint_event.send(EventDamageHintSpawn {
+				damage,
+				position,
+				kind: HitKind::Normal,
+			});
+		}
+	}
+
+	let aim = aim_direction(
+		settings.aim,
+		input.trigger(),
+		input.mouse.position,
+		player_tf,
+		&enemy_queries.p0(),
+		AIM_RANGE * stats.get(Stat::AttackRange),
+	);
+	let direction = match aim.and_then(|aim| Direction2d::new(aim).ok()) {
+		Some(val) => val,
+		None => {
+			*visibility = Visibility::Hidden;
+			beam.pending.clear();
+			return;
+		}
+	};
+
+	let origin = player_tf.translation.truncate();
+	let mut length = BEAM_LENGTH * stats.get(Stat::AttackRange);
+	// Aiming with the mouse the beam ends at the cursor
+	if settings.aim == Targeting::Mouse {
+		length = length.min(input.mouse.position.distance(origin));
+	}
+	*visibility = Visibility::Visible;
+	sprite.custom_size = Some(Vec2::new(BEAM_WIDTH, length));
+	transform.translation = origin.extend(0.5);
+	transform.rotation =
+		Quat::from_rotation_z(direction.y.atan2(direction.x) - f32::to_radians(90.0));
+
+	let ray = RayCast2d::new(origin, direction, length);
+	let damage = stats.get(Stat::BeamDamage) * time.delta_seconds();
+	// Leftovers of enemies that left the beam are dropped
+	let mut previous = std::mem::take(&mut beam.pending);
+	for (enemy, enemy_tf, mut killable) in enemy_queries.p1().iter_mut() {
+		if killable.hp <= 0 {
+			continue;
+		}
+		// Widen the enemy box by half the beam so the ray stands for the whole beam
+		let position = enemy_tf.translation.truncate();
+		let aabb = Aabb2d::new(position, ENEMY_HALF_SIZE + BEAM_WIDTH / 2.);
+		if ray.aabb_intersection_at(&aabb).is_none() {
+			continue;
+		}
+		let pending = previous.remove(&enemy).unwrap_or(0.) + damage;
+		let dealt = pending.floor();
+		beam.pending.insert(enemy, pending - dealt);
+		if dealt >= 1. {
+			killable.hit(dealt as i32);
+			let unshown = beam.unshown.entry(enemy).or_insert((0, position));
+			unshown.0 += dealt as u32;
+			unshown.1 = position;
+		}
+	}
+}
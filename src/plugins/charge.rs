@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+
+use crate::{
+	actions::Actions,
+	components::{
+		knockback::Impact,
+		stats::{PlayerStats, Stat},
+	},
+	constants::BULLET_RANGE,
+	loading::TextureAssets,
+	player::Player,
+	Bullet, FromPlayer, GameState, MaxRange, Mouse, Movable, Penetration, SceneObject,
+	SpriteSize, Velocity,
+};
+
+/// Seconds to reach a full charge
+pub const MAX_CHARGE: f32 = 1.5;
+const MIN_CHARGE: f32 = 0.1;
+const CHARGE_COOLDOWN: f32 = 2.0;
+const CHARGE_DAMAGE_SCALE: f32 = 4.0;
+const CHARGE_SIZE_SCALE: f32 = 1.5;
+const CHARGE_KNOCKBACK_SCALE: f32 = 2.0;
+const CHARGE_RANGE_SCALE: f32 = 1.5;
+/// Extra stun seconds of a full charge
+const CHARGE_STUN: f32 = 0.5;
+
+pub struct ChargeShotPlugin;
+
+/// Secondary fire, held to charge and released to shoot
+#[derive(Component)]
+pub struct ChargeShot {
+	pub charge: f32,
+	pub cooldown: Timer,
+}
+
+impl Default for ChargeShot {
+	fn default() -> Self {
+		let mut cooldown = Timer::from_seconds(CHARGE_COOLDOWN, TimerMode::Once);
+		cooldown.tick(cooldown.duration());
+		Self { charge: 0., cooldown }
+	}
+}
+
+impl ChargeShot {
+	pub fn ratio(&self) -> f32 {
+		(self.charge / MAX_CHARGE).clamp(0., 1.)
+	}
+}
+
+impl Plugin for ChargeShotPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Update, charge_shot_system.run_if(in_state(GameState::Playing)));
+	}
+}
+
+fn charge_shot_system(
+	mut commands: Commands,
+	actions: Res<Actions>,
+	mouse: Res<Mouse>,
+	time: Res<Time>,
+	textures: Res<TextureAssets>,
+	images: Res<Assets<Image>>,
+	mut query: Query<(&Transform, &PlayerStats, &mut ChargeShot), With<Player>>,
+) {
+	let (player_tf, stats, mut shot) = match query.get_single_mut() {
+		Ok(val) => val,
+		Err(_) => return,
+	};
+	shot.cooldown.tick(time.delta());
+	if !shot.cooldown.finished() {
+		return;
+	}
+	if actions.player_is_second_shooting {
+		shot.charge = (shot.charge + time.delta_seconds()).min(MAX_CHARGE);
+		return;
+	}
+	if shot.charge < MIN_CHARGE {
+		shot.charge = 0.;
+		return;
+	}
+
+	let ratio = shot.ratio();
+	shot.charge = 0.;
+	shot.cooldown.reset();
+
+	let origin = player_tf.translation.truncate();
+	let direction = match (mouse.position - origin).try_normalize() {
+		Some(val) => val,
+		None => return,
+	};
+	let texture = if ratio >= 1. {
+		textures.laser_b.clone()
+	} else {
+		textures.laser_a.clone()
+	};
+	let size = images.get(&texture).map_or(Vec2::new(9., 54.), |image| image.size_f32());
+	let scale = 1. + ratio * CHARGE_SIZE_SCALE;
+	let damage = stats.get(Stat::Damage) * (1. + ratio * CHARGE_DAMAGE_SCALE);
+	let penetration = stats.get(Stat::Penetration) as u32 + (ratio * 3.) as u32;
+
+	commands
+		.spawn(SpriteBundle {
+			texture,
+			transform: Transform {
+				translation: origin.extend(0.5),
+				rotation: Quat::from_rotation_z(
+					direction.y.atan2(direction.x) - f32::to_radians(90.0),
+				),
+				scale: Vec3::new(scale, scale, 1.),
+			},
+			..default()
+		})
+		.insert(Bullet {
+			damage: damage.round() as i32,
+		})
+		.insert(Penetration::new(penetration))
+		.insert(Impact {
+			knockback: stats.get(Stat::Knockback) * (1. + ratio * CHARGE_KNOCKBACK_SCALE),
+			stun: stats.get(Stat::Stun) + ratio * CHARGE_STUN,
+		})
+		.insert(SpriteSize(size))
+		.insert(MaxRange {
+			origin,
+			range: BULLET_RANGE * CHARGE_RANGE_SCALE * stats.get(Stat::AttackRange),
+		})
+		.insert(SceneObject)
+		.insert(FromPlayer)
+		.insert(Movable { auto_despawn: true })
+		.insert(Velocity {
+			x: direction.x,
+			y: direction.y,
+			speed: stats.get(Stat::BulletSpeed) * 1.5,
+		});
+}
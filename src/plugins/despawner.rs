@@ -3,40 +3,117 @@ use bevy::prelude::*;
 use crate::{
 	components::killable::Killable,
 	constants::DESPAWN_BULLET_DISTANCE,
+	meta::RunStats,
 	player::{Player, PlayerGetExpEvent},
-	Bullet, Enemy, GameState, Movable,
+	plugins::pool::{BulletParts, PoolCommandsExt},
+	Bullet, Enemy, GameState, Lifetime, MaxRange, Movable,
 };
 
 pub struct Despawner;
 
+/// Expired projectiles are found in `Detect` and released in `Release`,
+/// systems reacting to `EventProjectileExpired` run in between
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ExpireSet {
+	Detect,
+	Release,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ExpireReason {
+	Lifetime,
+	MaxRange,
+	/// Too far from the player to matter
+	OutOfView,
+}
+
+#[derive(Event)]
+pub struct EventProjectileExpired {
+	pub entity: Entity,
+	pub position: Vec2,
+	pub reason: ExpireReason,
+}
+
 impl Plugin for Despawner {
 	fn build(&self, app: &mut App) {
-		app.add_systems(Update, despawn_system.run_if(in_state(GameState::Playing)));
+		app.add_event::<EventProjectileExpired>()
+			.configure_sets(Update, (ExpireSet::Detect, ExpireSet::Release).chain())
+			.add_systems(
+				Update,
+				(
+					despawn_system.run_if(in_state(GameState::Playing)),
+					projectile_expire_system
+						.in_set(ExpireSet::Detect)
+						.run_if(in_state(GameState::Playing)),
+					release_expired_system
+						.in_set(ExpireSet::Release)
+						.run_if(in_state(GameState::Playing)),
+				),
+			);
 	}
 }
 
-fn despawn_system(
-	mut commands: Commands,
-	mut query: Query<(Entity, &Transform, &Movable), With<Bullet>>,
+/// Bullet with the limits it can expire by
+type ExpiringBullet = (
+	Entity,
+	&'static Transform,
+	&'static Movable,
+	Option<&'static mut Lifetime>,
+	Option<&'static MaxRange>,
+);
+
+fn projectile_expire_system(
+	time: Res<Time>,
+	mut query: Query<ExpiringBullet, With<Bullet>>,
 	player_q: Query<&Transform, With<Player>>,
-	killable_query: Query<(Entity, &Killable, &Enemy), With<Enemy>>,
-	mut exp_event: EventWriter<PlayerGetExpEvent>,
+	mut expired_event: EventWriter<EventProjectileExpired>,
 ) {
-	if let Ok(player) = player_q.get_single() {
-		for (entity, transform, movable) in query.iter_mut() {
-			if movable.auto_despawn {
-				let distance = transform.translation.distance(player.translation);
-				if DESPAWN_BULLET_DISTANCE < distance {
-					commands.entity(entity).despawn_recursive();
-					println!("despawn_recursive");
-				}
+	let player = match player_q.get_single() {
+		Ok(val) => val.translation.truncate(),
+		Err(_) => return,
+	};
+	for (entity, transform, movable, lifetime, max_range) in query.iter_mut() {
+		let position = transform.translation.truncate();
+		let mut reason = None;
+		if let Some(mut lifetime) = lifetime {
+			if lifetime.0.tick(time.delta()).just_finished() {
+				reason = Some(ExpireReason::Lifetime);
 			}
 		}
-		for (entity, killable, enemy) in killable_query.iter() {
-			if killable.hp <= 0 {
-				exp_event.send(PlayerGetExpEvent { exp: enemy.kill_exp });
-				commands.entity(entity).despawn_recursive();
+		if let Some(max_range) = max_range {
+			if position.distance(max_range.origin) > max_range.range {
+				reason = Some(ExpireReason::MaxRange);
 			}
 		}
+		if movable.auto_despawn && position.distance(player) > DESPAWN_BULLET_DISTANCE {
+			reason = Some(ExpireReason::OutOfView);
+		}
+		if let Some(reason) = reason {
+			expired_event.send(EventProjectileExpired { entity, position, reason });
+		}
+	}
+}
+
+fn release_expired_system(
+	mut commands: Commands,
+	mut expired_event: EventReader<EventProjectileExpired>,
+) {
+	for ev in expired_event.read() {
+		commands.release::<BulletParts>(ev.entity);
+	}
+}
+
+fn despawn_system(
+	mut commands: Commands,
+	killable_query: Query<(Entity, &Killable, &Enemy), With<Enemy>>,
+	mut exp_event: EventWriter<PlayerGetExpEvent>,
+	mut run_stats: ResMut<RunStats>,
+) {
+	for (entity, killable, enemy) in killable_query.iter() {
+		if killable.hp <= 0 {
+			exp_event.send(PlayerGetExpEvent { exp: enemy.kill_exp });
+			run_stats.kills += 1;
+			commands.entity(entity).despawn_recursive();
+		}
 	}
 }
@@ -0,0 +1,166 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+
+use crate::{
+	loading::{BulletAssets, BulletKind},
+	player::Player,
+	plugins::{pool::BulletPool, weapon::fan},
+	Bullet, FromEnemy, GameState, Movable, SceneObject, Velocity,
+};
+
+pub struct EmitterPlugin;
+
+impl Plugin for EmitterPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(Update, emitter_system.run_if(in_state(GameState::Playing)));
+	}
+}
+
+#[derive(Clone, Debug)]
+pub enum BulletPattern {
+	/// Bullets spread evenly around a full circle
+	Ring { count: u32 },
+	/// Ring that turns by angular_velocity (radians per second) between shots
+	Spiral { count: u32, angular_velocity: f32 },
+	/// Bullets fanned over spread radians around the direction to the player
+	Fan { count: u32, spread: f32 },
+	/// Single bullet aimed at the player, repeat the step for a burst
+	Aimed,
+}
+
+/// One entry of an emitter sequence, the pattern is shot `repeat` times
+#[derive(Clone, Debug)]
+pub struct PatternStep {
+	pub pattern: BulletPattern,
+	pub repeat: u32,
+	/// Seconds between two shots of the step
+	pub interval: f32,
+}
+
+impl PatternStep {
+	pub fn new(pattern: BulletPattern, repeat: u32, interval: f32) -> Self {
+		Self {
+			pattern,
+			repeat: repeat.max(1),
+			interval,
+		}
+	}
+}
+
+/// Projectile an emitter shoots
+#[derive(Clone, Debug)]
+pub struct EmitterBullet {
+	pub damage: i32,
+	pub speed: f32,
+	pub size: f32,
+	pub kind: BulletKind,
+}
+
+impl Default for EmitterBullet {
+	fn default() -> Self {
+		Self {
+			damage: 1,
+			speed: 150.,
+			size: 6.,
+			kind: BulletKind::Enemy,
+		}
+	}
+}
+
+/// Shoots hostile bullet patterns from the entity it is attached to, the sequence loops
+#[derive(Component)]
+pub struct BulletEmitter {
+	pub sequence: Vec<PatternStep>,
+	pub bullet: EmitterBullet,
+	step: usize,
+	shot: u32,
+	angle: f32,
+	timer: Timer,
+}
+
+impl BulletEmitter {
+	/// `delay` is the time before the first shot
+	pub fn new(sequence: Vec<PatternStep>, bullet: EmitterBullet, delay: f32) -> Self {
+		Self {
+			sequence,
+			bullet,
+			step: 0,
+			shot: 0,
+			angle: 0.,
+			timer: Timer::from_seconds(delay, TimerMode::Once),
+		}
+	}
+
+	/// Directions of the next shot, moves the sequence forward
+	fn next_shot(&mut self, aim: Vec2) -> Vec<Vec2> {
+		let step = match self.sequence.get(self.step) {
+			Some(val) => val.clone(),
+			None => return Vec::new(),
+		};
+		let directions = match step.pattern {
+			BulletPattern::Ring { count } => ring(count, 0.),
+			BulletPattern::Spiral { count, angular_velocity } => {
+				self.angle = (self.angle + angular_velocity * step.interval) % TAU;
+				ring(count, self.angle)
+			}
+			BulletPattern::Fan { count, spread } => fan(aim, count, spread),
+			BulletPattern::Aimed => vec![aim],
+		};
+
+		self.shot += 1;
+		if self.shot >= step.repeat {
+			self.shot = 0;
+			self.step = (self.step + 1) % self.sequence.len();
+		}
+		let interval = self.sequence[self.step].interval;
+		self.timer = Timer::from_seconds(interval, TimerMode::Once);
+		directions
+	}
+}
+
+fn ring(count: u32, offset: f32) -> Vec<Vec2> {
+	(0..count)
+		.map(|i| Vec2::from_angle(offset + TAU * i as f32 / count as f32))
+		.collect()
+}
+
+fn emitter_system(
+	mut commands: Commands,
+	time: Res<Time>,
+	bullet_assets: Res<BulletAssets>,
+	mut pool: ResMut<BulletPool>,
+	player_query: Query<&Transform, With<Player>>,
+	mut emitter_query: Query<(&GlobalTransform, &mut BulletEmitter)>,
+) {
+	let target = player_query.get_single().ok().map(|p| p.translation.truncate());
+	for (transform, mut emitter) in emitter_query.iter_mut() {
+		emitter.timer.tick(time.delta());
+		if !emitter.timer.finished() {
+			continue;
+		}
+		let origin = transform.translation().truncate();
+		let aim = target.and_then(|t| (t - origin).try_normalize()).unwrap_or(Vec2::X);
+		let bullet = emitter.bullet.clone();
+		for direction in emitter.next_shot(aim) {
+			let entity = pool.acquire(&mut commands);
+			commands
+				.entity(entity)
+				.insert(MaterialMesh2dBundle {
+					transform: Transform::from_translation(origin.extend(0.))
+						.with_scale(Vec3::new(bullet.size, bullet.size, 0.)),
+					..bullet_assets.bundle(bullet.kind)
+				})
+				.insert(Bullet { damage: bullet.damage })
+				.insert(SceneObject)
+				.insert(FromEnemy)
+				.insert(Movable { auto_despawn: true })
+				.insert(Velocity {
+					x: direction.x,
+					y: direction.y,
+					speed: bullet.speed,
+				});
+		}
+	}
+}
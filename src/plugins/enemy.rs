@@ -2,12 +2,16 @@ use bevy::prelude::*;
 use rand::{thread_rng, Rng};
 
 use crate::{
-	components::killable::Killable,
+	components::{killable::Killable, knockback::Knockback, status::StatusEffects},
 	loading::TextureAssets,
-	player::{Player, PlayerGetExpEvent},
+	player::Player,
+	plugins::emitter::{BulletEmitter, BulletPattern, EmitterBullet, PatternStep},
 	Enemy, GameData, GameState, SceneObject, Velocity,
 };
 
+/// Chance of a new enemy to shoot bullet patterns at the player
+const SHOOTER_CHANCE: f64 = 0.1;
+
 pub struct EnemySpawnPlugin;
 
 impl Plugin for EnemySpawnPlugin {
@@ -16,7 +20,6 @@ impl Plugin for EnemySpawnPlugin {
 			Update,
 			(
 				enemy_move_system.run_if(in_state(GameState::Playing)),
-				enemy_despawn_system.run_if(in_state(GameState::Playing)),
 				enemy_spawn_system.run_if(in_state(GameState::Playing)),
 			),
 		);
@@ -44,7 +47,7 @@ fn enemy_spawn_system(
 			translation.x += player.translation.x;
 			translation.y += player.translation.y;
 
-			commands
+			let enemy = commands
 				.spawn(SpriteBundle {
 					transform: Transform {
 						translation,
@@ -55,24 +58,58 @@ fn enemy_spawn_system(
 					..Default::default()
 				})
 				.insert(Killable::new(3, 3, false))
+				.insert(StatusEffects::default())
+				.insert(Knockback::default())
 				.insert(Velocity {
 					x: 0.5,
 					y: 0.5,
 					speed: 30.0,
 				})
 				.insert(SceneObject)
-				.insert(Enemy { damage: 1, kill_exp: 15 });
+				.insert(Enemy { damage: 1, kill_exp: 15 })
+				.id();
+
+			if rng.gen_bool(SHOOTER_CHANCE) {
+				commands.entity(enemy).insert(shooter_emitter(&mut rng));
+			}
 		}
 	}
 }
 
+fn shooter_emitter(rng: &mut impl Rng) -> BulletEmitter {
+	let bullet = EmitterBullet::default();
+	let sequence = match rng.gen_range(0..3) {
+		0 => vec![
+			PatternStep::new(BulletPattern::Aimed, 3, 0.2),
+			PatternStep::new(BulletPattern::Fan { count: 5, spread: 0.6 }, 1, 2.0),
+		],
+		1 => vec![PatternStep::new(BulletPattern::Ring { count: 8 }, 1, 3.0)],
+		_ => vec![PatternStep::new(
+			BulletPattern::Fan { count: 3, spread: 0.4 },
+			1,
+			2.5,
+		)],
+	};
+	BulletEmitter::new(sequence, bullet, 2.0)
+}
+
 fn enemy_move_system(
 	time: Res<Time>,
 	player_query: Query<&Transform, With<Player>>,
-	mut enemy_query: Query<(&mut Velocity, &mut Transform), (With<Enemy>, Without<Player>)>,
+	mut enemy_query: Query<
+		(&mut Velocity, &mut Transform, &mut Knockback),
+		(With<Enemy>, Without<Player>),
+	>,
 ) {
 	if let Ok(player) = player_query.get_single() {
-		for (mut velocity, mut enemy) in &mut enemy_query.iter_mut() {
+		for (mut velocity, mut enemy, mut knockback) in &mut enemy_query.iter_mut() {
+			let offset = knockback.step(time.delta_seconds());
+			enemy.translation += offset.extend(0.);
+
+			knockback.stun.tick(time.delta());
+			if knockback.is_stunned() {
+				continue;
+			}
 			let diff = player.translation.truncate() - enemy.translation.truncate();
 			velocity.x = diff.normalize().x;
 			velocity.y = diff.normalize().y;
@@ -82,16 +119,3 @@ fn enemy_move_system(
 		}
 	}
 }
-
-fn enemy_despawn_system(
-	mut commands: Commands,
-	query: Query<(Entity, &Killable), With<Enemy>>,
-	mut exp_event: EventWriter<PlayerGetExpEvent>,
-) {
-	for (entity, killable) in query.iter() {
-		if killable.hp <= 0 {
-			exp_event.send(PlayerGetExpEvent { exp: 15 });
-			commands.entity(entity).despawn();
-		}
-	}
-}
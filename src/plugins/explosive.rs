@@ -0,0 +1,172 @@
+use bevy::prelude::*;
+
+use crate::{
+	components::{
+		killable::Killable,
+		stats::{PlayerStats, Stat},
+	},
+	loading::TextureAssets,
+	player::Player,
+	plugins::{
+		despawner::{EventProjectileExpired, ExpireReason, ExpireSet},
+		weapon::Weapon,
+	},
+	ui::damage::{EventDamageHintSpawn, HitKind},
+	Enemy, GameState, SceneObject,
+};
+
+/// Share of the damage still dealt at the edge of the blast
+const EDGE_DAMAGE: f32 = 0.25;
+const EXPLOSION_FRAMES: usize = 16;
+const EXPLOSION_FRAME_TIME: f32 = 0.04;
+/// Size of one frame of the explosion sheet
+const EXPLOSION_TILE: f32 = 64.;
+
+pub struct ExplosivePlugin;
+
+impl Plugin for ExplosivePlugin {
+	fn build(&self, app: &mut App) {
+		app.add_event::<EventExplosion>().add_systems(
+			Update,
+			(
+				grenade_launcher_system.run_if(in_state(GameState::Playing)),
+				explode_on_expire_system
+					.after(ExpireSet::Detect)
+					.before(ExpireSet::Release)
+					.run_if(in_state(GameState::Playing)),
+				explosion_system.run_if(in_state(GameState::Playing)),
+				explosion_animation_system.run_if(in_state(GameState::Playing)),
+			),
+		);
+	}
+}
+
+/// Bullet that blows up on impact or when it expires
+#[derive(Component)]
+pub struct Explosive {
+	pub radius: f32,
+	pub damage: i32,
+}
+
+impl Explosive {
+	pub fn new(radius: f32, damage: i32) -> Self {
+		Self { radius, damage }
+	}
+
+	pub fn explode(&self, position: Vec2) -> EventExplosion {
+		EventExplosion {
+			position,
+			radius: self.radius,
+			damage: self.damage,
+		}
+	}
+}
+
+#[derive(Event)]
+pub struct EventExplosion {
+	pub position: Vec2,
+	pub radius: f32,
+	/// Damage in the center, it falls off toward the edge
+	pub damage: i32,
+}
+
+#[derive(Component)]
+struct ExplosionAnimation {
+	timer: Timer,
+}
+
+/// Marks the weapon given by the grenade skill
+#[derive(Component)]
+struct GrenadeLauncher;
+
+/// Keeps one launcher on the player while the grenade skill is taken
+fn grenade_launcher_system(
+	mut commands: Commands,
+	player_query: Query<(Entity, &PlayerStats), With<Player>>,
+	launcher_query: Query<&Weapon, With<GrenadeLauncher>>,
+) {
+	let (player, stats) = match player_query.get_single() {
+		Ok(val) => val,
+		Err(_) => return,
+	};
+	if stats.get(Stat::ExplosionRadius) <= 0. || !launcher_query.is_empty() {
+		return;
+	}
+	commands.entity(player).with_children(|parent| {
+		parent.spawn(Weapon::grenade_launcher()).insert(GrenadeLauncher);
+	});
+}
+
+fn explode_on_expire_system(
+	mut expired_event: EventReader<EventProjectileExpired>,
+	query: Query<&Explosive>,
+	mut explosion_event: EventWriter<EventExplosion>,
+) {
+	for ev in expired_event.read().filter(|ev| ev.reason != ExpireReason::OutOfView) {
+		if let Ok(explosive) = query.get(ev.entity) {
+			explosion_event.send(explosive.explode(ev.position));
+		}
+	}
+}
+
+fn explosion_system(
+	mut commands: Commands,
+	textures: Res<TextureAssets>,
+	mut event: EventReader<EventExplosion>,
+	mut enemy_query: Query<(&Transform, &mut Killable), With<Enemy>>,
+	mut damage_hint_event: EventWriter<EventDamageHintSpawn>,
+) {
+	for ev in event.read() {
+		for (transform, mut killable) in enemy_query.iter_mut() {
+			let position = transform.translation.truncate();
+			let distance = position.distance(ev.position);
+			if killable.hp <= 0 || distance > ev.radius {
+				continue;
+			}
+			let falloff = 1. - (1. - EDGE_DAMAGE) * distance / ev.radius;
+			let damage = ((ev.damage as f32 * falloff).round() as i32).max(1);
+			killable.hit(damage);
+			damage_hint_event.send(EventDamageHintSpawn {
+				damage: damage as u32,
+				position,
+				kind: HitKind::Normal,
+			});
+		}
+
+		let scale = ev.radius * 2. / EXPLOSION_TILE;
+		commands
+			.spawn(SpriteSheetBundle {
+				texture: textures.explosion.clone(),
+				atlas: TextureAtlas {
+					layout: textures.explosion_layout.clone(),
+					index: 0,
+				},
+				transform: Transform::from_translation(ev.position.extend(1.5))
+					.with_scale(Vec3::new(scale, scale, 1.)),
+				..default()
+			})
+			.insert(ExplosionAnimation {
+				timer: Timer::from_seconds(EXPLOSION_FRAME_TIME, TimerMode::Repeating),
+			})
+			.insert(SceneObject);
+	}
+}
+
+fn explosion_animation_system(
+	mut commands: Commands,
+	time: Res<Time>,
+	mut query: Query<(Entity, &mut ExplosionAnimation, &mut TextureAtlas)>,
+) {
+	for (entity, mut animation, mut atlas) in query.iter_mut() {
+		animation.timer.tick(time.delta());
+		let frames = animation.timer.times_finished_this_tick() as usize;
+		if frames == 0 {
+			continue;
+		}
+		if atlas.index + frames >= EXPLOSION_FRAMES {
+			commands.entity(entity).despawn_recursive();
+		} else {
+			atlas.index += frames;
+		}
+	}
+}
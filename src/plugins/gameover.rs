@@ -1,6 +1,11 @@
 use bevy::prelude::*;
 
-use crate::{loading::FontAssets, player::PlayerMove, GameState, MainCamera, SceneObject};
+use crate::{
+	loading::FontAssets,
+	meta::{run_reward, RunStats},
+	player::{Player, PlayerMove},
+	GameState, MainCamera, SceneObject,
+};
 
 pub struct GameOverPlugin;
 
@@ -21,7 +26,13 @@ fn on_key_press(
 	}
 }
 
-fn init_gameover(mut commands: Commands, font_assets: Res<FontAssets>) {
+fn init_gameover(
+	mut commands: Commands,
+	font_assets: Res<FontAssets>,
+	query: Query<&Player>,
+	run_stats: Res<RunStats>,
+) {
+	let reward = query.get_single().map_or(0, |p| run_reward(p.lvl, &run_stats));
 	commands
 		.spawn(NodeBundle {
 			style: Style {
@@ -41,7 +52,7 @@ fn init_gameover(mut commands: Commands, font_assets: Res<FontAssets>) {
 				.spawn((NodeBundle {
 					style: Style {
 						width: Val::Px(390.0),
-						height: Val::Px(75.0),
+						height: Val::Px(100.0),
 						justify_content: JustifyContent::Center,
 						align_items: AlignItems::Center,
 						flex_direction: FlexDirection::Column,
@@ -59,6 +70,19 @@ fn init_gameover(mut commands: Commands, font_assets: Res<FontAssets>) {
 						},
 					));
 				})
+				.with_children(|parent| {
+					parent.spawn(TextBundle::from_section(
+						format!(
+							"Kills: {}  Grazes: {}  Coins earned: {reward}",
+							run_stats.kills, run_stats.grazes
+						),
+						TextStyle {
+							font: font_assets.fira_sans.clone(),
+							font_size: 20.0,
+							color: Color::rgb(0.9, 0.8, 0.3),
+						},
+					));
+				})
 				.with_children(|parent| {
 					parent.spawn(TextBundle::from_section(
 						"Press SPACE key to continue",
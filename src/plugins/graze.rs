@@ -0,0 +1,110 @@
+use bevy::math::bounding::{Aabb2d, IntersectsVolume};
+use bevy::prelude::*;
+
+use crate::{
+	components::killable::Killable,
+	meta::RunStats,
+	player::{enemy_bullet_hit_system, Player},
+	plugins::{charge::ChargeShot, despawner::ExpireSet},
+	Enemy, FromEnemy, GameState, SpriteSize,
+};
+
+/// How far the graze ring reaches past the hurtbox
+const GRAZE_RING: f32 = 24.;
+/// Meter gained per graze, a full meter is 1
+const GRAZE_METER_GAIN: f32 = 0.1;
+
+pub struct GrazePlugin;
+
+impl Plugin for GrazePlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			Update,
+			(
+				// A bullet marked after it went back to the pool could never be grazed again
+				graze_system
+					.before(enemy_bullet_hit_system)
+					.before(ExpireSet::Release)
+					.run_if(in_state(GameState::Playing)),
+				graze_meter_system.run_if(in_state(GameState::Playing)),
+			),
+		);
+	}
+}
+
+/// Graze meter of the player, a full meter refreshes the charge shot
+#[derive(Component, Default)]
+pub struct GrazeMeter {
+	pub value: f32,
+}
+
+/// Where a hostile object is in its pass by the player
+#[derive(Component, PartialEq, Eq)]
+pub enum GrazeState {
+	/// Inside the ring without having touched the hurtbox
+	Inside,
+	/// Grazed or hit the player, never counted again
+	Done,
+}
+
+/// Enemies and their bullets, anything that can hurt the player
+type Hostile = (Or<(With<FromEnemy>, With<Enemy>)>, Without<Player>);
+/// Hostile with its graze progress, only enemies have hp
+type HostileGraze = (
+	Entity,
+	&'static Transform,
+	Option<&'static GrazeState>,
+	Option<&'static Killable>,
+);
+
+/// A graze counts once the object leaves the ring without touching the hurtbox
+fn graze_system(
+	mut commands: Commands,
+	mut player_query: Query<(&Transform, &SpriteSize, &mut GrazeMeter), With<Player>>,
+	hostile_query: Query<HostileGraze, Hostile>,
+	mut run_stats: ResMut<RunStats>,
+) {
+	let (player_transform, sprite, mut meter) = match player_query.get_single_mut() {
+		Ok(val) => val,
+		Err(_) => return,
+	};
+	let player_position = player_transform.translation.truncate();
+	let hurtbox_half = sprite.0 * player_transform.scale.truncate() / 2.;
+	let hurtbox = Aabb2d::new(player_position, hurtbox_half);
+	let ring = Aabb2d::new(player_position, hurtbox_half + GRAZE_RING);
+
+	for (entity, transform, state, killable) in hostile_query.iter() {
+		// Dead enemies wait for their despawn and can not graze
+		if state == Some(&GrazeState::Done) || killable.is_some_and(|k| k.hp <= 0) {
+			continue;
+		}
+		let aabb =
+			Aabb2d::new(transform.translation.truncate(), transform.scale.truncate() / 2.);
+		// The entity may be despawned before the commands run
+		if aabb.intersects(&hurtbox) {
+			commands.entity(entity).try_insert(GrazeState::Done);
+		} else if aabb.intersects(&ring) {
+			if state.is_none() {
+				commands.entity(entity).try_insert(GrazeState::Inside);
+			}
+		} else if state == Some(&GrazeState::Inside) {
+			commands.entity(entity).try_insert(GrazeState::Done);
+			run_stats.grazes += 1;
+			meter.value = (meter.value + GRAZE_METER_GAIN).min(1.);
+		}
+	}
+}
+
+fn graze_meter_system(
+	mut player_query: Query<(&mut GrazeMeter, &mut ChargeShot), With<Player>>,
+) {
+	let (mut meter, mut shot) = match player_query.get_single_mut() {
+		Ok(val) => val,
+		Err(_) => return,
+	};
+	if meter.value >= 1. && !shot.cooldown.finished() {
+		meter.value = 0.;
+		let duration = shot.cooldown.duration();
+		shot.cooldown.tick(duration);
+	}
+}
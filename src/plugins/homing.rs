@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+
+use crate::{
+	components::{
+		killable::Killable,
+		stats::{PlayerStats, Stat},
+	},
+	player::Player,
+	plugins::weapon::Weapon,
+	Enemy, GameState, Penetration, Velocity,
+};
+
+/// Radians per second a missile can turn
+const HOMING_TURN_RATE: f32 = 4.;
+
+pub struct HomingPlugin;
+
+impl Plugin for HomingPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			Update,
+			(
+				missile_launcher_system.run_if(in_state(GameState::Playing)),
+				homing_system.run_if(in_state(GameState::Playing)),
+			),
+		);
+	}
+}
+
+/// Steers the bullet toward an enemy, a new one is picked when the target dies
+#[derive(Component)]
+pub struct Homing {
+	pub target: Option<Entity>,
+	pub turn_rate: f32,
+}
+
+impl Default for Homing {
+	fn default() -> Self {
+		Self {
+			target: None,
+			turn_rate: HOMING_TURN_RATE,
+		}
+	}
+}
+
+/// Marks the weapon given by the missile skill
+#[derive(Component)]
+struct MissileLauncher;
+
+/// Keeps one launcher on the player while the missile skill is taken,
+/// every rank adds a missile to the volley
+fn missile_launcher_system(
+	mut commands: Commands,
+	player_query: Query<(Entity, &PlayerStats), With<Player>>,
+	mut launcher_query: Query<&mut Weapon, With<MissileLauncher>>,
+) {
+	let (player, stats) = match player_query.get_single() {
+		Ok(val) => val,
+		Err(_) => return,
+	};
+	let count = stats.get(Stat::MissileCount) as u32;
+	if count == 0 {
+		return;
+	}
+	match launcher_query.get_single_mut() {
+		Ok(mut weapon) => weapon.count = count,
+		Err(_) => {
+			commands.entity(player).with_children(|parent| {
+				parent.spawn(Weapon::missile_launcher(count)).insert(MissileLauncher);
+			});
+		}
+	}
+}
+
+fn homing_system(
+	time: Res<Time>,
+	mut bullet_query: Query<(&Transform, &mut Velocity, &mut Homing, Option<&Penetration>)>,
+	enemy_query: Query<(Entity, &Transform, &Killable), With<Enemy>>,
+) {
+	for (transform, mut velocity, mut homing, penetration) in bullet_query.iter_mut() {
+		let position = transform.translation.truncate();
+		// Enemies the bullet already went through are not worth chasing
+		let valid = |enemy: Entity, killable: &Killable| {
+			killable.hp > 0 && !penetration.is_some_and(|p| p.is_hit(enemy))
+		};
+		let alive = homing
+			.target
+			.and_then(|target| enemy_query.get(target).ok())
+			.filter(|(enemy, _, killable)| valid(*enemy, killable));
+		if alive.is_none() {
+			homing.target = enemy_query
+				.iter()
+				.filter(|(enemy, _, killable)| valid(*enemy, killable))
+				.min_by(|(_, a, _), (_, b, _)| {
+					let a = a.translation.truncate().distance(position);
+					let b = b.translation.truncate().distance(position);
+					a.total_cmp(&b)
+				})
+				.map(|(enemy, _, _)| enemy);
+		}
+		let target = match homing.target.and_then(|target| enemy_query.get(target).ok()) {
+			Some((_, target, _)) => target.translation.truncate(),
+			None => continue,
+		};
+
+		let desired = match (target - position).try_normalize() {
+			Some(val) => val,
+			None => continue,
+		};
+		let heading = Vec2::new(velocity.x, velocity.y).try_normalize().unwrap_or(desired);
+		let max_turn = homing.turn_rate * time.delta_seconds();
+		let turn = heading.angle_between(desired).clamp(-max_turn, max_turn);
+		let direction = Vec2::from_angle(turn).rotate(heading);
+		velocity.x = direction.x;
+		velocity.y = direction.y;
+	}
+}
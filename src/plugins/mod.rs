@@ -1,3 +1,13 @@
+pub mod beam;
+pub mod charge;
 pub mod despawner;
+pub mod emitter;
 pub mod enemy;
+pub mod explosive;
 pub mod gameover;
+pub mod graze;
+pub mod homing;
+pub mod pool;
+pub mod spirit;
+pub mod status;
+pub mod weapon;
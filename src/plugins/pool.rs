@@ -0,0 +1,114 @@
+use std::marker::PhantomData;
+
+use bevy::ecs::system::Command;
+use bevy::hierarchy::despawn_with_children_recursive;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+use crate::{
+	components::{knockback::Impact, status::ApplyStatus},
+	plugins::{explosive::Explosive, graze::GrazeState, homing::Homing},
+	ui::damage::DamageHint,
+	Bullet, FromEnemy, FromPlayer, Lifetime, MaxRange, Movable, Penetration, Ricochet,
+	SceneObject, Velocity,
+};
+
+/// Gameplay components a bullet loses when it goes back to the pool
+pub type BulletParts = (
+	Bullet,
+	Penetration,
+	Ricochet,
+	ApplyStatus,
+	Impact,
+	Homing,
+	Explosive,
+	FromPlayer,
+	FromEnemy,
+	Movable,
+	Velocity,
+	Lifetime,
+	MaxRange,
+	GrazeState,
+	SceneObject,
+);
+pub type BulletPool = Pool<BulletParts>;
+
+/// Gameplay components a damage hint loses when it goes back to the pool
+pub type HintParts = (DamageHint,);
+pub type HintPool = Pool<HintParts>;
+
+pub struct PoolPlugin;
+
+impl Plugin for PoolPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<BulletPool>().init_resource::<HintPool>();
+	}
+}
+
+/// Marks entities created by a pool, only those are recycled on release
+#[derive(Component)]
+pub struct Pooled;
+
+/// Hidden entities kept alive to be reused instead of spawned again,
+/// `T` is the bundle stripped from an entity when it is released
+#[derive(Resource)]
+pub struct Pool<T: Bundle> {
+	free: HashSet<Entity>,
+	_parts: PhantomData<fn() -> T>,
+}
+
+impl<T: Bundle> Default for Pool<T> {
+	fn default() -> Self {
+		Self {
+			free: HashSet::new(),
+			_parts: PhantomData,
+		}
+	}
+}
+
+impl<T: Bundle> Pool<T> {
+	/// Reuse a released entity or spawn a new one, the caller inserts the components
+	pub fn acquire(&mut self, commands: &mut Commands) -> Entity {
+		match self.free.iter().next().copied() {
+			Some(entity) => {
+				self.free.remove(&entity);
+				entity
+			}
+			None => commands.spawn(Pooled).id(),
+		}
+	}
+}
+
+struct Release<T: Bundle> {
+	entity: Entity,
+	_parts: PhantomData<fn() -> T>,
+}
+
+impl<T: Bundle> Command for Release<T> {
+	fn apply(self, world: &mut World) {
+		let mut entity = match world.get_entity_mut(self.entity) {
+			Some(val) => val,
+			None => return,
+		};
+		if !entity.contains::<Pooled>() {
+			despawn_with_children_recursive(world, self.entity);
+			return;
+		}
+		entity.remove::<T>().insert(Visibility::Hidden);
+		world.resource_mut::<Pool<T>>().free.insert(self.entity);
+	}
+}
+
+pub trait PoolCommandsExt {
+	/// Hide the entity and hand it back to `Pool<T>`, entities not made by a pool are despawned
+	fn release<T: Bundle>(&mut self, entity: Entity);
+}
+
+impl PoolCommandsExt for Commands<'_, '_> {
+	fn release<T: Bundle>(&mut self, entity: Entity) {
+		self.add(Release::<T> {
+			entity,
+			_parts: PhantomData,
+		});
+	}
+}
@@ -0,0 +1,163 @@
+use std::f32::consts::TAU;
+
+use bevy::math::bounding::{Aabb2d, IntersectsVolume};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::{
+	components::{
+		killable::Killable,
+		stats::{PlayerStats, Stat},
+	},
+	constants::ENEMY_HALF_SIZE,
+	loading::TextureAssets,
+	player::Player,
+	ui::damage::{EventDamageHintSpawn, HitKind},
+	Enemy, GameState,
+};
+
+pub struct SpiritPlugin;
+
+#[derive(Resource)]
+pub struct SpiritSettings {
+	/// Radians per second
+	pub angular_speed: f32,
+	pub radius: f32,
+	pub size: f32,
+	/// Seconds before the same spirit can hit the same enemy again
+	pub hit_cooldown: f32,
+}
+
+impl Default for SpiritSettings {
+	fn default() -> Self {
+		Self {
+			angular_speed: 2.5,
+			radius: 60.,
+			size: 20.,
+			hit_cooldown: 0.5,
+		}
+	}
+}
+
+#[derive(Component, Default)]
+pub struct Spirit {
+	cooldowns: HashMap<Entity, Timer>,
+}
+
+impl Plugin for SpiritPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<SpiritSettings>().add_systems(
+			Update,
+			(
+				spirit_count_system.run_if(in_state(GameState::Playing)),
+				spirit_orbit_system.run_if(in_state(GameState::Playing)),
+				spirit_hit_system.run_if(in_state(GameState::Playing)),
+			),
+		);
+	}
+}
+
+fn spirit_count_system(
+	mut commands: Commands,
+	textures: Res<TextureAssets>,
+	settings: Res<SpiritSettings>,
+	player_query: Query<(Entity, &PlayerStats), With<Player>>,
+	spirit_query: Query<Entity, With<Spirit>>,
+) {
+	let (player, stats) = match player_query.get_single() {
+		Ok(val) => val,
+		Err(_) => return,
+	};
+	let count = stats.get(Stat::SpiritCount) as usize;
+	let current = spirit_query.iter().count();
+	if current < count {
+		commands.entity(player).with_children(|parent| {
+			for _ in current..count {
+				parent
+					.spawn(SpriteBundle {
+						texture: textures.texture_bevy.clone(),
+						sprite: Sprite {
+							custom_size: Some(Vec2::splat(settings.size)),
+							..default()
+						},
+						..default()
+					})
+					.insert(Spirit::default());
+			}
+		});
+	}
+	for entity in spirit_query.iter().skip(count) {
+		commands.entity(entity).despawn_recursive();
+	}
+}
+
+fn spirit_orbit_system(
+	time: Res<Time>,
+	settings: Res<SpiritSettings>,
+	mut angle: Local<f32>,
+	player_query: Query<&Transform, With<Player>>,
+	mut spirit_query: Query<&mut Transform, (With<Spirit>, Without<Player>)>,
+) {
+	let player = match player_query.get_single() {
+		Ok(val) => val,
+		Err(_) => return,
+	};
+	*angle = (*angle + settings.angular_speed * time.delta_seconds()) % TAU;
+	let count = spirit_query.iter().count();
+	for (index, mut transform) in spirit_query.iter_mut().enumerate() {
+		let spirit_angle = *angle + TAU * index as f32 / count as f32;
+		let offset = Vec2::from_angle(spirit_angle) * settings.radius;
+		// Spirits are children of the player, undo its rotation and scale
+		let local = player.rotation.inverse() * offset.extend(0.1);
+		transform.translation = local / player.scale;
+		transform.rotation = player.rotation.inverse();
+		transform.scale = player.scale.recip();
+	}
+}
+
+fn spirit_hit_system(
+	time: Res<Time>,
+	settings: Res<SpiritSettings>,
+	player_query: Query<&PlayerStats, With<Player>>,
+	mut spirit_query: Query<(&GlobalTransform, &mut Spirit)>,
+	mut enemy_query: Query<(Entity, &Transform, &mut Killable), With<Enemy>>,
+	mut damage_hint_event: EventWriter<EventDamageHintSpawn>,
+) {
+	let damage = match player_query.get_single() {
+		Ok(stats) => stats.get(Stat::SpiritDamage).round() as i32,
+		Err(_) => return,
+	};
+	for (spirit_transform, mut spirit) in spirit_query.iter_mut() {
+		for timer in spirit.cooldowns.values_mut() {
+			timer.tick(time.delta());
+		}
+		spirit.cooldowns.retain(|_, timer| !timer.finished());
+
+		let spirit_aabb = Aabb2d::new(
+			spirit_transform.translation().truncate(),
+			Vec2::splat(settings.size / 2.),
+		);
+		for (enemy_entity, enemy_transform, mut killable) in enemy_query.iter_mut() {
+			if killable.hp <= 0 || spirit.cooldowns.contains_key(&enemy_entity) {
+				continue;
+			}
+			let collision = spirit_aabb.intersects(&Aabb2d::new(
+				enemy_transform.translation.truncate(),
+				ENEMY_HALF_SIZE,
+			));
+
+			if collision {
+				killable.hit(damage);
+				damage_hint_event.send(EventDamageHintSpawn {
+					damage: damage as u32,
+					position: enemy_transform.translation.truncate(),
+					kind: HitKind::Normal,
+				});
+				spirit.cooldowns.insert(
+					enemy_entity,
+					Timer::from_seconds(settings.hit_cooldown, TimerMode::Once),
+				);
+			}
+		}
+	}
+}
@@ -0,0 +1,65 @@
+use bevy::prelude::*;
+
+use crate::{
+	components::{
+		killable::Killable,
+		status::{StatusEffect, StatusEffects},
+	},
+	ui::damage::{EventDamageHintSpawn, HitKind},
+	Enemy, GameState,
+};
+
+pub struct StatusEffectPlugin;
+
+#[derive(Event)]
+pub struct EventApplyStatus {
+	pub target: Entity,
+	pub effect: StatusEffect,
+}
+
+impl Plugin for StatusEffectPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_event::<EventApplyStatus>().add_systems(
+			Update,
+			(
+				apply_status_event.run_if(in_state(GameState::Playing)),
+				status_tick_system.run_if(in_state(GameState::Playing)),
+			),
+		);
+	}
+}
+
+fn apply_status_event(
+	mut event: EventReader<EventApplyStatus>,
+	mut query: Query<&mut StatusEffects, With<Enemy>>,
+) {
+	for ev in event.read() {
+		if let Ok(mut effects) = query.get_mut(ev.target) {
+			effects.apply(ev.effect.clone());
+		}
+	}
+}
+
+fn status_tick_system(
+	time: Res<Time>,
+	mut query: Query<(&Transform, &mut Killable, &mut StatusEffects), With<Enemy>>,
+	mut damage_hint_event: EventWriter<EventDamageHintSpawn>,
+) {
+	for (transform, mut killable, mut effects) in query.iter_mut() {
+		for effect in effects.0.iter_mut() {
+			effect.duration.tick(time.delta());
+			effect.tick.tick(time.delta());
+			if killable.hp <= 0 || !effect.tick.just_finished() {
+				continue;
+			}
+			let damage = effect.tick_damage();
+			killable.hit(damage);
+			damage_hint_event.send(EventDamageHintSpawn {
+				damage: damage as u32,
+				position: transform.translation.truncate(),
+				kind: HitKind::Dot(effect.kind),
+			});
+		}
+		effects.0.retain(|e| !e.duration.finished());
+	}
+}
@@ -0,0 +1,440 @@
+use std::time::Duration;
+
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::sprite::MaterialMesh2dBundle;
+
+use crate::{
+	components::{
+		killable::Killable,
+		knockback::Impact,
+		stats::{PlayerStats, Stat},
+		status::ApplyStatus,
+	},
+	constants::{AIM_RANGE, BULLET_LIFETIME, BULLET_RANGE, CLUSTER_RADIUS, RICOCHET_RADIUS},
+	loading::{BulletAssets, BulletKind},
+	player::Player,
+	plugins::{explosive::Explosive, homing::Homing, pool::BulletPool},
+	settings::Settings,
+	Bullet, Enemy, FromPlayer, GameState, Lifetime, MaxRange, Mouse, Movable, Penetration,
+	Ricochet, SceneObject, Velocity,
+};
+
+pub struct WeaponPlugin;
+
+impl Plugin for WeaponPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			Update,
+			(
+				main_weapon_aim_system.run_if(in_state(GameState::Playing)),
+				weapon_fire_system.run_if(in_state(GameState::Playing)),
+			),
+		);
+	}
+}
+
+/// How a weapon picks the direction to shoot
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Targeting {
+	/// Toward the mouse cursor, only while the fire button is held
+	Mouse,
+	/// Toward the closest enemy, fires on its own
+	Nearest,
+	/// Toward the enemy with the least hp in aim range, fires on its own
+	Weakest,
+	/// Toward the middle of the biggest enemy group in aim range, fires on its own
+	Cluster,
+}
+
+impl Targeting {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Targeting::Mouse => "Mouse",
+			Targeting::Nearest => "Nearest",
+			Targeting::Weakest => "Weakest",
+			Targeting::Cluster => "Cluster",
+		}
+	}
+}
+
+/// Projectile a weapon shoots, scales are applied on top of the player stats
+#[derive(Clone)]
+pub struct Projectile {
+	pub damage_scale: f32,
+	pub speed_scale: f32,
+	pub size_scale: f32,
+	pub knockback_scale: f32,
+	/// Distance from the muzzle before attack range is applied
+	pub range: f32,
+	/// Seconds before attack range is applied
+	pub lifetime: f32,
+	pub kind: BulletKind,
+	/// Steer toward enemies after launch
+	pub homing: bool,
+	/// Blow up on impact, the blast radius comes from the player stats
+	pub explosive: bool,
+}
+
+impl Default for Projectile {
+	fn default() -> Self {
+		Self {
+			damage_scale: 1.,
+			speed_scale: 1.,
+			size_scale: 1.,
+			knockback_scale: 1.,
+			range: BULLET_RANGE,
+			lifetime: BULLET_LIFETIME,
+			kind: BulletKind::Player,
+			homing: false,
+			explosive: false,
+		}
+	}
+}
+
+/// Marks the weapon the player starts with, it aims with the mode picked in the menu
+#[derive(Component)]
+pub struct MainWeapon;
+
+/// Weapon entity parented to the player, every weapon fires on its own cooldown
+#[derive(Component)]
+pub struct Weapon {
+	pub name: &'static str,
+	/// Seconds between volleys before attack speed is applied
+	pub cooldown: f32,
+	pub timer: Timer,
+	pub projectile: Projectile,
+	/// Angle in radians the volley is fanned over
+	pub spread: f32,
+	pub count: u32,
+	pub targeting: Targeting,
+}
+
+impl Weapon {
+	pub fn new(name: &'static str, cooldown: f32, targeting: Targeting) -> Self {
+		Self {
+			name,
+			cooldown,
+			timer: Timer::from_seconds(cooldown, TimerMode::Once),
+			projectile: Projectile::default(),
+			spread: 0.,
+			count: 1,
+			targeting,
+		}
+	}
+
+	pub fn blaster() -> Self {
+		Weapon::new("Blaster", 1., Targeting::Mouse)
+	}
+
+	/// Slow homing missiles fired on their own, `count` per volley
+	pub fn missile_launcher(count: u32) -> Self {
+		Weapon::new("Missile launcher", 2., Targeting::Nearest)
+			.with_projectile(Projectile {
+				damage_scale: 1.5,
+				speed_scale: 0.5,
+				size_scale: 1.2,
+				range: BULLET_RANGE * 3.,
+				lifetime: 4.,
+				homing: true,
+				..default()
+			})
+			.with_volley(count, 1.2)
+	}
+
+	/// Slow grenades lobbed at the biggest enemy group
+	pub fn grenade_launcher() -> Self {
+		Weapon::new("Grenade launcher", 2.5, Targeting::Cluster).with_projectile(Projectile {
+			damage_scale: 2.,
+			speed_scale: 0.5,
+			size_scale: 1.5,
+			range: BULLET_RANGE / 2.,
+			lifetime: 1.2,
+			explosive: true,
+			..default()
+		})
+	}
+
+	pub fn with_projectile(mut self, projectile: Projectile) -> Self {
+		self.projectile = projectile;
+		self
+	}
+
+	pub fn with_targeting(mut self, targeting: Targeting) -> Self {
+		self.targeting = targeting;
+		self
+	}
+
+	pub fn with_volley(mut self, count: u32, spread: f32) -> Self {
+		self.count = count.max(1);
+		self.spread = spread;
+		self
+	}
+}
+
+/// Directions of `count` projectiles fanned over `spread` radians around the aim direction
+pub fn fan(aim: Vec2, count: u32, spread: f32) -> Vec<Vec2> {
+	if count <= 1 {
+		return vec![aim];
+	}
+	let step = spread / (count - 1) as f32;
+	(0..count)
+		.map(|i| Vec2::from_angle(-spread / 2. + step * i as f32).rotate(aim))
+		.collect()
+}
+
+/// Final values of one projectile after player stats are applied
+pub struct ProjectileStats {
+	pub damage: i32,
+	pub size: f32,
+	pub speed: f32,
+	pub penetration: u32,
+	pub ricochet: u32,
+	pub ricochet_chance: f32,
+	pub status: ApplyStatus,
+	pub impact: Impact,
+	pub range: f32,
+	pub lifetime: f32,
+	pub homing: bool,
+	/// Blast radius, zero for bullets that do not explode
+	pub explosion_radius: f32,
+}
+
+impl ProjectileStats {
+	pub fn new(stats: &PlayerStats, projectile: &Projectile) -> Self {
+		Self {
+			damage: (stats.get(Stat::Damage) * projectile.damage_scale).round() as i32,
+			size: stats.get(Stat::BulletSize) * projectile.size_scale,
+			speed: stats.get(Stat::BulletSpeed) * projectile.speed_scale,
+			penetration: stats.get(Stat::Penetration) as u32,
+			ricochet: stats.get(Stat::RicochetCount) as u32,
+			ricochet_chance: stats.get(Stat::RicochetChance),
+			status: ApplyStatus {
+				burning: stats.get(Stat::TimeDamage).round() as i32,
+				poison: stats.get(Stat::PoisonDamage).round() as i32,
+				duration: stats.get(Stat::TimeDamageDuration),
+			},
+			impact: Impact {
+				knockback: stats.get(Stat::Knockback) * projectile.knockback_scale,
+				stun: stats.get(Stat::Stun),
+			},
+			range: projectile.range * stats.get(Stat::AttackRange),
+			lifetime: projectile.lifetime * stats.get(Stat::AttackRange),
+			homing: projectile.homing,
+			explosion_radius: if projectile.explosive {
+				stats.get(Stat::ExplosionRadius)
+			} else {
+				0.
+			},
+		}
+	}
+}
+
+pub fn spawn_player_bullet(
+	commands: &mut Commands,
+	pool: &mut BulletPool,
+	bundle: MaterialMesh2dBundle<ColorMaterial>,
+	origin: Vec2,
+	direction: Vec2,
+	projectile: &ProjectileStats,
+) {
+	let size = projectile.size;
+	let entity = pool.acquire(commands);
+	let mut bullet = commands.entity(entity);
+	bullet
+		.insert(MaterialMesh2dBundle {
+			transform: Transform::from_translation(origin.extend(0.)).with_scale(Vec3 {
+				x: size,
+				y: size,
+				z: 0.0,
+			}),
+			..bundle
+		})
+		.insert(Bullet {
+			damage: projectile.damage,
+		})
+		.insert(Penetration::new(projectile.penetration))
+		.insert(projectile.impact)
+		.insert(Lifetime::new(projectile.lifetime))
+		.insert(MaxRange {
+			origin,
+			range: projectile.range,
+		})
+		.insert(Ricochet {
+			charges: projectile.ricochet,
+			chance: projectile.ricochet_chance,
+			radius: RICOCHET_RADIUS,
+		})
+		.insert(SceneObject)
+		.insert(FromPlayer)
+		.insert(Movable { auto_despawn: true })
+		.insert(Velocity {
+			x: direction.x,
+			y: direction.y,
+			speed: projectile.speed,
+		});
+	if !projectile.status.is_empty() {
+		bullet.insert(projectile.status);
+	}
+	if projectile.homing {
+		bullet.insert(Homing::default());
+	}
+	if projectile.explosion_radius > 0. {
+		bullet.insert(Explosive::new(projectile.explosion_radius, projectile.damage));
+	}
+}
+
+/// Closest of the given enemy positions, callers filter out the ones that do not count
+pub fn nearest_enemy(enemies: impl Iterator<Item = Vec2>, position: Vec2) -> Option<Vec2> {
+	enemies.min_by(|a, b| a.distance(position).total_cmp(&b.distance(position)))
+}
+
+fn weakest_enemy(
+	enemy_query: &Query<(&Transform, &Killable), With<Enemy>>,
+	position: Vec2,
+	range: f32,
+) -> Option<Vec2> {
+	enemy_query
+		.iter()
+		.map(|(transform, killable)| (transform.translation.truncate(), killable.hp))
+		.filter(|(target, hp)| *hp > 0 && target.distance(position) <= range)
+		.min_by_key(|(_, hp)| *hp)
+		.map(|(target, _)| target)
+}
+
+/// Center of the enemy in range with the most other enemies around it
+fn densest_cluster(
+	enemy_query: &Query<(&Transform, &Killable), With<Enemy>>,
+	position: Vec2,
+	range: f32,
+) -> Option<Vec2> {
+	let enemies: Vec<Vec2> = enemy_query
+		.iter()
+		.filter(|(_, killable)| killable.hp > 0)
+		.map(|(transform, _)| transform.translation.truncate())
+		.filter(|target| target.distance(position) <= range)
+		.collect();
+	enemies
+		.iter()
+		.map(|center| {
+			let group: Vec<&Vec2> =
+				enemies.iter().filter(|e| e.distance(*center) <= CLUSTER_RADIUS).collect();
+			let sum: Vec2 = group.iter().copied().sum();
+			(group.len(), sum / group.len() as f32)
+		})
+		.max_by_key(|(count, _)| *count)
+		.map(|(_, center)| center)
+}
+
+/// Normalized direction to shoot at, None while there is nothing to shoot
+pub fn aim_direction(
+	targeting: Targeting,
+	trigger: bool,
+	mouse: Vec2,
+	player_tf: &Transform,
+	enemy_query: &Query<(&Transform, &Killable), With<Enemy>>,
+	range: f32,
+) -> Option<Vec2> {
+	let origin = player_tf.translation.truncate();
+	let aim = match targeting {
+		Targeting::Mouse if trigger => Some(mouse - origin),
+		Targeting::Mouse => None,
+		Targeting::Nearest => {
+			let alive = enemy_query
+				.iter()
+				.filter(|(_, killable)| killable.hp > 0)
+				.map(|(transform, _)| transform.translation.truncate());
+			nearest_enemy(alive, origin).map(|e| e - origin)
+		}
+		Targeting::Weakest => weakest_enemy(enemy_query, origin, range).map(|e| e - origin),
+		Targeting::Cluster => densest_cluster(enemy_query, origin, range).map(|e| e - origin),
+	};
+	aim.and_then(|a| a.try_normalize())
+}
+
+/// The aim mode can be changed from the menu in the middle of a run
+fn main_weapon_aim_system(
+	settings: Res<Settings>,
+	mut query: Query<&mut Weapon, With<MainWeapon>>,
+) {
+	if !settings.is_changed() {
+		return;
+	}
+	for mut weapon in query.iter_mut() {
+		weapon.targeting = settings.aim;
+	}
+}
+
+/// Fire buttons and cursor the weapons aim with
+#[derive(SystemParam)]
+pub struct FireInput<'w> {
+	kb: Res<'w, ButtonInput<KeyCode>>,
+	buttons: Res<'w, ButtonInput<MouseButton>>,
+	pub mouse: Res<'w, Mouse>,
+}
+
+impl FireInput<'_> {
+	pub fn trigger(&self) -> bool {
+		self.kb.pressed(KeyCode::Space) || self.buttons.pressed(MouseButton::Left)
+	}
+}
+
+/// What a weapon needs to put its bullets in the world
+#[derive(SystemParam)]
+struct BulletSpawner<'w, 's> {
+	commands: Commands<'w, 's>,
+	assets: Res<'w, BulletAssets>,
+	pool: ResMut<'w, BulletPool>,
+}
+
+fn weapon_fire_system(
+	input: FireInput,
+	mut spawner: BulletSpawner,
+	player_query: Query<(&Transform, &PlayerStats), With<Player>>,
+	mut weapon_query: Query<&mut Weapon>,
+	enemy_query: Query<(&Transform, &Killable), With<Enemy>>,
+	time: Res<Time>,
+) {
+	let (player_tf, stats) = match player_query.get_single() {
+		Ok(val) => val,
+		Err(_) => return,
+	};
+	let origin = player_tf.translation.truncate();
+	let trigger = input.trigger();
+	let range = AIM_RANGE * stats.get(Stat::AttackRange);
+	for mut weapon in weapon_query.iter_mut() {
+		weapon.timer.tick(time.delta());
+		if !weapon.timer.finished() {
+			continue;
+		}
+		let aim = aim_direction(
+			weapon.targeting,
+			trigger,
+			input.mouse.position,
+			player_tf,
+			&enemy_query,
+			range,
+		);
+		let aim = match aim {
+			Some(val) => val,
+			None => continue,
+		};
+
+		let cooldown = weapon.cooldown / stats.get(Stat::AttackSpeed).max(0.01);
+		weapon.timer.set_duration(Duration::from_secs_f32(cooldown));
+		weapon.timer.reset();
+
+		let projectile = ProjectileStats::new(stats, &weapon.projectile);
+		for direction in fan(aim, weapon.count, weapon.spread) {
+			let bundle = spawner.assets.bundle(weapon.projectile.kind);
+			spawn_player_bullet(
+				&mut spawner.commands,
+				&mut spawner.pool,
+				bundle,
+				origin,
+				direction,
+				&projectile,
+			);
+		}
+	}
+}
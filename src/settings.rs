@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+
+use crate::plugins::weapon::Targeting;
+
+/// Aim modes the player can pick for the main weapon
+pub const AIM_MODES: [Targeting; 4] = [
+	Targeting::Mouse,
+	Targeting::Nearest,
+	Targeting::Weakest,
+	Targeting::Cluster,
+];
+
+#[derive(Resource)]
+pub struct Settings {
+	pub aim: Targeting,
+}
+
+impl Default for Settings {
+	fn default() -> Self {
+		Self { aim: Targeting::Mouse }
+	}
+}
+
+impl Settings {
+	pub fn next_aim(&mut self) {
+		let index = AIM_MODES.iter().position(|mode| *mode == self.aim).unwrap_or(0);
+		self.aim = AIM_MODES[(index + 1) % AIM_MODES.len()];
+	}
+}
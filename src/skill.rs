@@ -1,6 +1,207 @@
-use bevy::prelude::Image;
+use bevy::prelude::{Color, Handle, Image, Resource};
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
+use rand::{thread_rng, Rng};
 
-enum SkillType {
+use crate::components::stats::{Modifier, ModifierKind, ModifierSource, Stat};
+use crate::loading::TextureAssets;
+
+pub const SKILLS_PER_DRAW: usize = 3;
+pub const REROLL_CHARGES: u32 = 3;
+pub const BANISH_CHARGES: u32 = 2;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Rarity {
+	Common,
+	Rare,
+	Epic,
+	Legendary,
+}
+
+impl Rarity {
+	pub const ALL: [Rarity; 4] = [
+		Rarity::Common,
+		Rarity::Rare,
+		Rarity::Epic,
+		Rarity::Legendary,
+	];
+
+	pub fn weight(&self) -> u32 {
+		match self {
+			Rarity::Common => 60,
+			Rarity::Rare => 25,
+			Rarity::Epic => 12,
+			Rarity::Legendary => 3,
+		}
+	}
+
+	/// Multiplier range applied to the skill value
+	pub fn value_range(&self) -> (f32, f32) {
+		match self {
+			Rarity::Common => (0.8, 1.0),
+			Rarity::Rare => (1.2, 1.5),
+			Rarity::Epic => (1.7, 2.0),
+			Rarity::Legendary => (2.5, 3.0),
+		}
+	}
+
+	pub fn name(&self) -> &'static str {
+		match self {
+			Rarity::Common => "Common",
+			Rarity::Rare => "Rare",
+			Rarity::Epic => "Epic",
+			Rarity::Legendary => "Legendary",
+		}
+	}
+
+	pub fn color(&self) -> Color {
+		match self {
+			Rarity::Common => Color::rgb(0.6, 0.6, 0.6),
+			Rarity::Rare => Color::rgb(0.25, 0.5, 1.0),
+			Rarity::Epic => Color::rgb(0.65, 0.3, 0.9),
+			Rarity::Legendary => Color::rgb(1.0, 0.65, 0.1),
+		}
+	}
+
+	pub fn roll() -> Self {
+		let dist = WeightedIndex::new(Rarity::ALL.iter().map(|r| r.weight())).unwrap();
+		Rarity::ALL[dist.sample(&mut thread_rng())]
+	}
+}
+
+/// Late-game upgrade unlocked by maxing both skills of its recipe
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Evolution {
+	RailShot,
+	Inferno,
+	ChainLightning,
+	SpiritStorm,
+}
+
+pub struct Recipe {
+	pub evolution: Evolution,
+	pub requires: [SkillType; 2],
+}
+
+pub const RECIPES: [Recipe; 4] = [
+	Recipe {
+		evolution: Evolution::RailShot,
+		requires: [SkillType::AttackSpeed, SkillType::Penetration],
+	},
+	Recipe {
+		evolution: Evolution::Inferno,
+		requires: [SkillType::TimeDamage, SkillType::TimeDamageDuration],
+	},
+	Recipe {
+		evolution: Evolution::ChainLightning,
+		requires: [SkillType::RicochetCount, SkillType::RicochetChance],
+	},
+	Recipe {
+		evolution: Evolution::SpiritStorm,
+		requires: [SkillType::Spirit, SkillType::SpiritDamage],
+	},
+];
+
+impl Evolution {
+	pub fn name(&self) -> &'static str {
+		match self {
+			Evolution::RailShot => "Rail shot",
+			Evolution::Inferno => "Inferno",
+			Evolution::ChainLightning => "Chain lightning",
+			Evolution::SpiritStorm => "Spirit storm",
+		}
+	}
+
+	pub fn description(&self) -> &'static str {
+		match self {
+			Evolution::RailShot => "Bullets fly twice as fast and pierce every enemy",
+			Evolution::Inferno => "Burning deals double damage",
+			Evolution::ChainLightning => "Bullets always ricochet, +2 ricochets",
+			Evolution::SpiritStorm => "+2 spirits, spirits deal double damage",
+		}
+	}
+
+	pub fn modifiers(&self) -> Vec<(Stat, ModifierKind, f32)> {
+		match self {
+			Evolution::RailShot => vec![
+				(Stat::Penetration, ModifierKind::Flat, 1000.),
+				(Stat::BulletSpeed, ModifierKind::Percent, 1.),
+			],
+			Evolution::Inferno => vec![(Stat::TimeDamage, ModifierKind::Percent, 1.)],
+			Evolution::ChainLightning => vec![
+				(Stat::RicochetChance, ModifierKind::Flat, 1.),
+				(Stat::RicochetCount, ModifierKind::Flat, 2.),
+			],
+			Evolution::SpiritStorm => vec![
+				(Stat::SpiritCount, ModifierKind::Flat, 2.),
+				(Stat::SpiritDamage, ModifierKind::Percent, 1.),
+			],
+		}
+	}
+}
+
+/// Card shown on level-up
+#[derive(Clone)]
+pub enum Offer {
+	Skill(Skill),
+	Evolution(Evolution),
+}
+
+impl Offer {
+	pub fn name(&self) -> String {
+		match self {
+			Offer::Skill(skill) => format!("{} {}", skill.name, skill.rank + 1),
+			Offer::Evolution(evolution) => evolution.name().to_string(),
+		}
+	}
+
+	pub fn description(&self) -> String {
+		match self {
+			Offer::Skill(skill) => skill.description(),
+			Offer::Evolution(evolution) => evolution.description().to_string(),
+		}
+	}
+
+	pub fn label(&self) -> &'static str {
+		match self {
+			Offer::Skill(skill) => skill.rarity.name(),
+			Offer::Evolution(_) => "Evolution",
+		}
+	}
+
+	pub fn color(&self) -> Color {
+		match self {
+			Offer::Skill(skill) => skill.rarity.color(),
+			Offer::Evolution(_) => Color::rgb(1.0, 0.25, 0.25),
+		}
+	}
+
+	pub fn icon(&self, textures: &TextureAssets) -> Handle<Image> {
+		match self {
+			Offer::Skill(skill) => skill.mode.icon(textures),
+			Offer::Evolution(_) => textures.texture_bevy.clone(),
+		}
+	}
+}
+
+/// Reroll and banish uses left for the current run
+#[derive(Resource)]
+pub struct SkillCharges {
+	pub rerolls: u32,
+	pub banishes: u32,
+}
+
+impl Default for SkillCharges {
+	fn default() -> Self {
+		Self {
+			rerolls: REROLL_CHARGES,
+			banishes: BANISH_CHARGES,
+		}
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum SkillType {
 	AttackSpeed,
 	AttackRange,
 	DamageFlat,
@@ -11,29 +212,286 @@ enum SkillType {
 	BulletSize,
 	TimeDamage,
 	TimeDamageDuration,
+	Poison,
 	RicochetCount,
 	RicochetChance,
 	Spirit,
 	SpiritDamage,
+	Missile,
+	Beam,
+	Grenade,
+	CritChance,
+	CritDamage,
+	Knockback,
+	Stun,
 }
 
-struct Skill {
-	icon: Image,
-	name: String,
-	mode: SkillType,
-	value: f32,
+impl SkillType {
+	/// Player stat that one rank of the skill modifies
+	pub fn stat(&self) -> (Stat, ModifierKind) {
+		match self {
+			SkillType::AttackSpeed => (Stat::AttackSpeed, ModifierKind::Percent),
+			SkillType::AttackRange => (Stat::AttackRange, ModifierKind::Percent),
+			SkillType::DamageFlat => (Stat::Damage, ModifierKind::Flat),
+			SkillType::MoveSpeed => (Stat::MoveSpeed, ModifierKind::Percent),
+			SkillType::DamagePercent => (Stat::Damage, ModifierKind::Percent),
+			SkillType::MaxHp => (Stat::MaxHp, ModifierKind::Flat),
+			SkillType::Penetration => (Stat::Penetration, ModifierKind::Flat),
+			SkillType::BulletSize => (Stat::BulletSize, ModifierKind::Percent),
+			SkillType::TimeDamage => (Stat::TimeDamage, ModifierKind::Flat),
+			SkillType::TimeDamageDuration => (Stat::TimeDamageDuration, ModifierKind::Flat),
+			SkillType::Poison => (Stat::PoisonDamage, ModifierKind::Flat),
+			SkillType::RicochetCount => (Stat::RicochetCount, ModifierKind::Flat),
+			SkillType::RicochetChance => (Stat::RicochetChance, ModifierKind::Flat),
+			SkillType::Spirit => (Stat::SpiritCount, ModifierKind::Flat),
+			SkillType::SpiritDamage => (Stat::SpiritDamage, ModifierKind::Flat),
+			SkillType::Missile => (Stat::MissileCount, ModifierKind::Flat),
+			SkillType::Beam => (Stat::BeamDamage, ModifierKind::Flat),
+			SkillType::Grenade => (Stat::ExplosionRadius, ModifierKind::Flat),
+			SkillType::CritChance => (Stat::CritChance, ModifierKind::Flat),
+			SkillType::CritDamage => (Stat::CritMultiplier, ModifierKind::Flat),
+			SkillType::Knockback => (Stat::Knockback, ModifierKind::Percent),
+			SkillType::Stun => (Stat::Stun, ModifierKind::Flat),
+		}
+	}
+
+	/// Skills that only make sense in whole steps
+	pub fn is_integer(&self) -> bool {
+		matches!(
+			self,
+			SkillType::DamageFlat
+				| SkillType::MaxHp
+				| SkillType::Penetration
+				| SkillType::TimeDamage
+				| SkillType::Poison
+				| SkillType::RicochetCount
+				| SkillType::Spirit
+				| SkillType::SpiritDamage
+				| SkillType::Missile
+				| SkillType::Beam
+				| SkillType::Grenade
+		)
+	}
+
+	pub fn icon(&self, textures: &TextureAssets) -> Handle<Image> {
+		match self {
+			SkillType::MaxHp => textures.heart.clone(),
+			SkillType::MoveSpeed => textures.player.clone(),
+			SkillType::Spirit | SkillType::SpiritDamage => textures.texture_bevy.clone(),
+			SkillType::Missile => textures.laser_b.clone(),
+			SkillType::Beam => textures.laser_a.clone(),
+			SkillType::Grenade => textures.explosion.clone(),
+			_ => textures.enemy.clone(),
+		}
+	}
+}
+
+#[derive(Clone)]
+pub struct Skill {
+	pub name: String,
+	pub mode: SkillType,
+	pub value: f32,
+	pub weight: u32,
+	pub max_rank: u32,
+	pub rank: u32,
+	pub rarity: Rarity,
+	pub banished: bool,
+}
+
+impl Skill {
+	pub fn new(name: &str, mode: SkillType, value: f32, weight: u32, max_rank: u32) -> Self {
+		Self {
+			name: name.to_string(),
+			mode,
+			value,
+			weight,
+			max_rank,
+			rank: 0,
+			rarity: Rarity::Common,
+			banished: false,
+		}
+	}
+
+	/// Copy of the skill with a random rarity and the value scaled by it
+	pub fn roll_offer(&self) -> Skill {
+		let rarity = Rarity::roll();
+		let (min, max) = rarity.value_range();
+		let value = self.value * thread_rng().gen_range(min..=max);
+		let value = if self.mode.is_integer() {
+			value.round().max(1.)
+		} else {
+			(value * 100.).round() / 100.
+		};
+		Skill {
+			rarity,
+			value,
+			..self.clone()
+		}
+	}
+
+	pub fn is_maxed(&self) -> bool {
+		self.rank >= self.max_rank
+	}
+
+	pub fn description(&self) -> String {
+		let value = self.value;
+		let percent = (self.value * 100.).round();
+		match self.mode {
+			SkillType::AttackSpeed => format!("+{percent}% attack speed"),
+			SkillType::AttackRange => format!("+{percent}% attack range"),
+			SkillType::DamageFlat => format!("+{value} bullet damage"),
+			SkillType::MoveSpeed => format!("+{percent}% move speed"),
+			SkillType::DamagePercent => format!("+{percent}% damage"),
+			SkillType::MaxHp => format!("+{value} max HP"),
+			SkillType::Penetration => format!("Bullets pierce {value} more enemy"),
+			SkillType::BulletSize => format!("+{percent}% bullet size"),
+			SkillType::TimeDamage => format!("Bullets burn for {value} damage per tick"),
+			SkillType::TimeDamageDuration => {
+				format!("Burning and poison last {value}s longer")
+			}
+			SkillType::Poison => {
+				format!("Bullets poison for {value} damage per tick, stacking")
+			}
+			SkillType::RicochetCount => format!("Bullets ricochet {value} more time"),
+			SkillType::RicochetChance => format!("+{percent}% ricochet chance"),
+			SkillType::Spirit => format!("+{value} orbiting spirit"),
+			SkillType::SpiritDamage => format!("+{value} spirit damage"),
+			SkillType::Missile => format!("+{value} homing missile per volley"),
+			SkillType::Beam => format!("+{value} laser beam damage per second"),
+			SkillType::Grenade => format!("+{value} grenade blast radius"),
+			SkillType::CritChance => format!("+{percent}% crit chance"),
+			SkillType::CritDamage => format!("+{percent}% crit damage"),
+			SkillType::Knockback => format!("+{percent}% knockback"),
+			SkillType::Stun => format!("Hits stun enemies {value}s longer"),
+		}
+	}
+
+	pub fn modifier(&self) -> (Stat, Modifier) {
+		let (stat, kind) = self.mode.stat();
+		let source = ModifierSource::Skill(self.mode);
+		(
+			stat,
+			Modifier {
+				kind,
+				source,
+				value: self.value,
+			},
+		)
+	}
 }
 
-struct SkillManager {
+#[derive(Resource)]
+pub struct SkillManager {
 	skills: Vec<Skill>,
+	evolutions: Vec<Evolution>,
 }
 
-//impl Default for SkillManager {
-//	fn default() -> Self {
-//		let skills = Vec::new();
-//	}
-//}
-//
-//impl SkillManager {
-//	pub fn get_skills() -> [Skill; 3] {}
-//}
+impl Default for SkillManager {
+	fn default() -> Self {
+		let skills = vec![
+			Skill::new("Attack speed", SkillType::AttackSpeed, 0.1, 10, 5),
+			Skill::new("Attack range", SkillType::AttackRange, 0.1, 8, 5),
+			Skill::new("Damage", SkillType::DamageFlat, 1., 10, 5),
+			Skill::new("Move speed", SkillType::MoveSpeed, 0.1, 8, 5),
+			Skill::new("Damage %", SkillType::DamagePercent, 0.1, 8, 5),
+			Skill::new("Max HP", SkillType::MaxHp, 2., 10, 5),
+			Skill::new("Penetration", SkillType::Penetration, 1., 5, 3),
+			Skill::new("Bullet size", SkillType::BulletSize, 0.2, 6, 5),
+			Skill::new("Burning", SkillType::TimeDamage, 1., 5, 5),
+			Skill::new("Burning duration", SkillType::TimeDamageDuration, 0.5, 4, 3),
+			Skill::new("Poison", SkillType::Poison, 1., 5, 5),
+			Skill::new("Ricochet", SkillType::RicochetCount, 1., 5, 3),
+			Skill::new("Ricochet chance", SkillType::RicochetChance, 0.15, 4, 4),
+			Skill::new("Spirit", SkillType::Spirit, 1., 4, 4),
+			Skill::new("Spirit damage", SkillType::SpiritDamage, 1., 4, 5),
+			Skill::new("Homing missile", SkillType::Missile, 1., 3, 4),
+			Skill::new("Laser beam", SkillType::Beam, 3., 3, 5),
+			Skill::new("Grenades", SkillType::Grenade, 40., 3, 4),
+			Skill::new("Crit chance", SkillType::CritChance, 0.05, 6, 5),
+			Skill::new("Crit damage", SkillType::CritDamage, 0.25, 5, 5),
+			Skill::new("Knockback", SkillType::Knockback, 0.25, 5, 5),
+			Skill::new("Stun", SkillType::Stun, 0.1, 4, 4),
+		];
+		Self {
+			skills,
+			evolutions: Vec::new(),
+		}
+	}
+}
+
+impl SkillManager {
+	/// Draw up to three distinct offers, a ready evolution takes the first card and
+	/// the rest are skills that are not maxed or banished, each with a rarity
+	pub fn get_skills(&self) -> Vec<Offer> {
+		let mut rng = thread_rng();
+		let mut pool: Vec<&Skill> =
+			self.skills.iter().filter(|s| !s.is_maxed() && !s.banished).collect();
+		let mut offers = Vec::with_capacity(SKILLS_PER_DRAW);
+		if let Some(evolution) = self.ready_evolutions().first() {
+			offers.push(Offer::Evolution(*evolution));
+		}
+		while offers.len() < SKILLS_PER_DRAW && !pool.is_empty() {
+			let dist = match WeightedIndex::new(pool.iter().map(|s| s.weight)) {
+				Ok(val) => val,
+				Err(_) => break,
+			};
+			offers.push(Offer::Skill(pool.swap_remove(dist.sample(&mut rng)).roll_offer()));
+		}
+		offers
+	}
+
+	/// Evolutions whose recipe skills are all maxed and which are not taken yet
+	pub fn ready_evolutions(&self) -> Vec<Evolution> {
+		RECIPES
+			.iter()
+			.filter(|r| !self.evolutions.contains(&r.evolution))
+			.filter(|r| {
+				r.requires.iter().all(|mode| self.get(*mode).is_some_and(|s| s.is_maxed()))
+			})
+			.map(|r| r.evolution)
+			.collect()
+	}
+
+	pub fn has_evolution(&self, evolution: Evolution) -> bool {
+		self.evolutions.contains(&evolution)
+	}
+
+	/// Take the evolution, returns false if it was already taken
+	pub fn evolve(&mut self, evolution: Evolution) -> bool {
+		if self.has_evolution(evolution) {
+			return false;
+		}
+		self.evolutions.push(evolution);
+		true
+	}
+
+	pub fn get(&self, mode: SkillType) -> Option<&Skill> {
+		self.skills.iter().find(|s| s.mode == mode)
+	}
+
+	/// Take one rank of the offered skill, returns false if it is already maxed
+	pub fn level_up(&mut self, offer: &Skill) -> bool {
+		match self.skills.iter_mut().find(|s| s.mode == offer.mode) {
+			Some(skill) if !skill.is_maxed() => {
+				skill.rank += 1;
+				true
+			}
+			_ => false,
+		}
+	}
+
+	/// Remove the skill from the draw pool for the rest of the run
+	pub fn banish(&mut self, mode: SkillType) {
+		if let Some(skill) = self.skills.iter_mut().find(|s| s.mode == mode) {
+			skill.banished = true;
+		}
+	}
+
+	pub fn reset(&mut self) {
+		self.evolutions.clear();
+		for skill in self.skills.iter_mut() {
+			skill.rank = 0;
+			skill.banished = false;
+		}
+	}
+}
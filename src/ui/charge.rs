@@ -0,0 +1,101 @@
+use bevy::prelude::*;
+
+use crate::{plugins::charge::ChargeShot, GameState};
+
+#[derive(Component)]
+struct ChargeBar;
+
+pub struct PlayerChargeBar;
+
+impl Plugin for PlayerChargeBar {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			Update,
+			(
+				spawn_charge_bar.run_if(in_state(GameState::Playing)),
+				update_charge_bar.run_if(in_state(GameState::Playing)),
+			),
+		);
+	}
+}
+
+fn get_charge_bar_color(shot: &ChargeShot) -> Color {
+	if !shot.cooldown.finished() {
+		Color::DARK_GRAY
+	} else if shot.ratio() >= 1. {
+		Color::ORANGE_RED
+	} else {
+		Color::ORANGE
+	}
+}
+
+fn get_charge_bar_style(shot: &ChargeShot) -> Style {
+	let percent = if shot.cooldown.finished() {
+		shot.ratio() * 100.
+	} else {
+		shot.cooldown.fraction() * 100.
+	};
+	Style {
+		width: Val::Percent(percent),
+		height: Val::Percent(100.0),
+		..Default::default()
+	}
+}
+
+fn update_charge_bar(
+	query: Query<&ChargeShot>,
+	mut cb_query: Query<(&mut Style, &mut BackgroundColor), With<ChargeBar>>,
+) {
+	if let Ok((mut style, mut color)) = cb_query.get_single_mut() {
+		if let Ok(shot) = query.get_single() {
+			*style = get_charge_bar_style(shot);
+			*color = get_charge_bar_color(shot).into();
+		}
+	}
+}
+
+fn spawn_charge_bar(
+	mut commands: Commands,
+	query: Query<&ChargeShot>,
+	cb_query: Query<&ChargeBar>,
+) {
+	if cb_query.iter().len() > 0 {
+		return;
+	}
+	if let Ok(shot) = query.get_single() {
+		commands
+			.spawn(NodeBundle {
+				style: Style {
+					position_type: PositionType::Absolute,
+					width: Val::Percent(100.0),
+					height: Val::Percent(100.0),
+					align_items: AlignItems::End,
+					justify_content: JustifyContent::Center,
+					..default()
+				},
+				..default()
+			})
+			.with_children(|parent| {
+				parent
+					.spawn((NodeBundle {
+						background_color: BackgroundColor(Color::rgba(0.3, 0.3, 0.3, 0.6)),
+						style: Style {
+							width: Val::Px(120.0),
+							height: Val::Px(6.0),
+							margin: UiRect::bottom(Val::Px(16.0)),
+							..Default::default()
+						},
+						..Default::default()
+					},))
+					.with_children(|parent| {
+						parent
+							.spawn(NodeBundle {
+								background_color: get_charge_bar_color(shot).into(),
+								style: get_charge_bar_style(shot),
+								..Default::default()
+							})
+							.insert(ChargeBar);
+					});
+			});
+	}
+}
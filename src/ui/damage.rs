@@ -1,15 +1,73 @@
 use bevy::prelude::*;
 
-use crate::{loading::FontAssets, GameState};
+use crate::{
+	components::status::StatusKind,
+	loading::FontAssets,
+	plugins::pool::{HintParts, HintPool, PoolCommandsExt},
+	GameState,
+};
 
 const HINT_SPEED: f32 = 20.0;
 const HINT_TIME: f32 = 1.0;
+/// Seconds a new hint takes to shrink from its pop size back to normal
+const HINT_POP_TIME: f32 = 0.15;
+pub const HINT_COLOR: Color = Color::rgb(0.98, 0.92, 0.84);
+pub const HINT_COLOR_CRIT: Color = Color::rgb(1.0, 0.85, 0.1);
+pub const HINT_COLOR_HEAL: Color = Color::rgb(0.35, 1.0, 0.6);
+pub const HINT_COLOR_BURNING: Color = Color::rgb(1.0, 0.55, 0.1);
+pub const HINT_COLOR_POISON: Color = Color::rgb(0.5, 0.9, 0.2);
 
 pub struct DamageHintPlugin;
 
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HitKind {
+	Normal,
+	Crit,
+	/// Damage over time, colored by the status that dealt it
+	Dot(StatusKind),
+	Heal,
+}
+
+impl HitKind {
+	fn color(&self) -> Color {
+		match self {
+			HitKind::Normal => HINT_COLOR,
+			HitKind::Crit => HINT_COLOR_CRIT,
+			HitKind::Dot(status) => status.hint_color(),
+			HitKind::Heal => HINT_COLOR_HEAL,
+		}
+	}
+
+	fn font_size(&self) -> f32 {
+		match self {
+			HitKind::Normal | HitKind::Heal => 12.0,
+			HitKind::Crit => 18.0,
+			HitKind::Dot(_) => 10.0,
+		}
+	}
+
+	/// Scale the hint starts at before it settles
+	fn pop(&self) -> f32 {
+		match self {
+			HitKind::Normal | HitKind::Heal => 1.3,
+			HitKind::Crit => 2.0,
+			HitKind::Dot(_) => 1.0,
+		}
+	}
+
+	fn label(&self, damage: u32) -> String {
+		match self {
+			HitKind::Crit => format!("{damage}!"),
+			HitKind::Heal => format!("+{damage}"),
+			HitKind::Normal | HitKind::Dot(_) => format!("{damage}"),
+		}
+	}
+}
+
 #[derive(Component)]
 pub struct DamageHint {
 	damage: u32,
+	kind: HitKind,
 	timer: Timer,
 }
 
@@ -17,6 +75,7 @@ pub struct DamageHint {
 pub struct EventDamageHintSpawn {
 	pub damage: u32,
 	pub position: Vec2,
+	pub kind: HitKind,
 }
 
 impl Plugin for DamageHintPlugin {
@@ -31,29 +90,34 @@ impl Plugin for DamageHintPlugin {
 	}
 }
 
-fn get_text_element(damage: u32, fa: Handle<Font>, transparency: f32) -> Text {
+fn get_text_element(damage: u32, fa: Handle<Font>, kind: HitKind, transparency: f32) -> Text {
 	let style = TextStyle {
 		font: fa,
-		font_size: 12.0,
-		color: Color::rgba(0.98, 0.92, 0.84, transparency),
+		font_size: kind.font_size(),
+		color: kind.color().with_a(transparency),
 	};
-	Text::from_section(format!("{damage}"), style).with_justify(JustifyText::Center)
+	Text::from_section(kind.label(damage), style).with_justify(JustifyText::Center)
 }
 
 fn spawn_damage_hint(
 	mut commands: Commands,
 	f_assets: Res<FontAssets>,
+	mut pool: ResMut<HintPool>,
 	mut event: EventReader<EventDamageHintSpawn>,
 ) {
 	for ev in event.read() {
+		let entity = pool.acquire(&mut commands);
 		commands
-			.spawn(Text2dBundle {
-				text: get_text_element(ev.damage, f_assets.fira_sans.clone(), 1.0),
-				transform: Transform::from_xyz(ev.position.x, ev.position.y, 2.0),
+			.entity(entity)
+			.insert(Text2dBundle {
+				text: get_text_element(ev.damage, f_assets.fira_sans.clone(), ev.kind, 1.0),
+				transform: Transform::from_xyz(ev.position.x, ev.position.y, 2.0)
+					.with_scale(Vec3::splat(ev.kind.pop())),
 				..Default::default()
 			})
 			.insert(DamageHint {
 				damage: ev.damage,
+				kind: ev.kind,
 				timer: Timer::from_seconds(HINT_TIME, TimerMode::Once),
 			});
 	}
@@ -67,13 +131,21 @@ fn damage_hint_animation(
 ) {
 	for (mut transform, mut text, mut damage_hint, entity) in query.iter_mut() {
 		if damage_hint.timer.finished() {
-			commands.entity(entity).despawn();
+			commands.release::<HintParts>(entity);
 		} else {
 			damage_hint.timer.tick(time.delta());
 		}
 		transform.translation.y += time.delta_seconds() * HINT_SPEED;
+		let settle = (damage_hint.timer.elapsed_secs() / HINT_POP_TIME).min(1.);
+		let scale = damage_hint.kind.pop() + (1. - damage_hint.kind.pop()) * settle;
+		transform.scale = Vec3::splat(scale);
 
 		let transparency = 1.0 - damage_hint.timer.elapsed().as_secs_f32() * 20.0 / HINT_SPEED;
-		*text = get_text_element(damage_hint.damage, f_assets.fira_sans.clone(), transparency);
+		*text = get_text_element(
+			damage_hint.damage,
+			f_assets.fira_sans.clone(),
+			damage_hint.kind,
+			transparency,
+		);
 	}
 }
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::{plugins::graze::GrazeMeter, GameState};
+
+const GRAZE_BAR_COLOR: Color = Color::rgb(0.4, 0.8, 1.0);
+
+#[derive(Component)]
+struct GrazeBar;
+
+pub struct PlayerGrazeBar;
+
+impl Plugin for PlayerGrazeBar {
+	fn build(&self, app: &mut App) {
+		app.add_systems(
+			Update,
+			(
+				spawn_graze_bar.run_if(in_state(GameState::Playing)),
+				update_graze_bar.run_if(in_state(GameState::Playing)),
+			),
+		);
+	}
+}
+
+fn get_graze_bar_style(meter: &GrazeMeter) -> Style {
+	Style {
+		width: Val::Percent(meter.value * 100.),
+		height: Val::Percent(100.0),
+		..Default::default()
+	}
+}
+
+fn update_graze_bar(
+	query: Query<&GrazeMeter>,
+	mut gb_query: Query<&mut Style, With<GrazeBar>>,
+) {
+	if let Ok(mut style) = gb_query.get_single_mut() {
+		if let Ok(meter) = query.get_single() {
+			*style = get_graze_bar_style(meter);
+		}
+	}
+}
+
+fn spawn_graze_bar(
+	mut commands: Commands,
+	query: Query<&GrazeMeter>,
+	gb_query: Query<&GrazeBar>,
+) {
+	if gb_query.iter().len() > 0 {
+		return;
+	}
+	if let Ok(meter) = query.get_single() {
+		commands
+			.spawn(NodeBundle {
+				style: Style {
+					position_type: PositionType::Absolute,
+					width: Val::Percent(100.0),
+					height: Val::Percent(100.0),
+					align_items: AlignItems::End,
+					justify_content: JustifyContent::Center,
+					..default()
+				},
+				..default()
+			})
+			.with_children(|parent| {
+				parent
+					.spawn((NodeBundle {
+						background_color: BackgroundColor(Color::rgba(0.3, 0.3, 0.3, 0.6)),
+						style: Style {
+							width: Val::Px(120.0),
+							height: Val::Px(3.0),
+							margin: UiRect::bottom(Val::Px(26.0)),
+							..Default::default()
+						},
+						..Default::default()
+					},))
+					.with_children(|parent| {
+						parent
+							.spawn(NodeBundle {
+								background_color: GRAZE_BAR_COLOR.into(),
+								style: get_graze_bar_style(meter),
+								..Default::default()
+							})
+							.insert(GrazeBar);
+					});
+			});
+	}
+}
@@ -1,57 +1,213 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
 
 use crate::{
-	components::killable::Killable,
+	components::stats::{Modifier, ModifierSource, PlayerStats},
 	loading::{FontAssets, TextureAssets},
-	player::Player,
+	player::{Player, PlayerLeveledUp},
+	skill::{Offer, SkillCharges, SkillManager},
 	GameState,
 };
 
-#[derive(Bundle)]
-struct HealthBarBundle {
-	text: TextBundle,
-}
+const CARD_NORMAL: Color = Color::rgb(0.15, 0.15, 0.15);
+const CARD_SELECTED: Color = Color::rgb(0.35, 0.25, 0.45);
+const CARD_KEYS: [KeyCode; 3] = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3];
+
+#[derive(Component)]
+struct LevelupMenuRoot;
 
 #[derive(Component)]
-struct HealthBarComponent {
-	hp: i32,
-	hp_max: i32,
+struct SkillCard(usize);
+
+#[derive(Component, Clone, Copy)]
+enum OfferAction {
+	Reroll,
+	Banish,
+}
+
+/// Level-ups that still wait for a skill pick
+#[derive(Resource, Default)]
+struct PendingLevelUps(u32);
+
+#[derive(Resource, Default)]
+struct SkillOffers {
+	cards: Vec<Offer>,
+	selected: usize,
 }
 
 pub struct LevelupMenu;
 
 impl Plugin for LevelupMenu {
 	fn build(&self, app: &mut App) {
-		app.add_systems(OnEnter(GameState::Upgrade), spawn_lvlup_menu)
-			.add_systems(Update, update_lvlup_menu.run_if(in_state(GameState::Upgrade)))
-			.add_systems(OnExit(GameState::Upgrade), despawn_lvlup_menu);
+		app.init_resource::<PendingLevelUps>()
+			.add_systems(
+				Update,
+				(
+					queue_level_ups.run_if(in_state(GameState::Playing)),
+					spawn_lvlup_menu.run_if(
+						in_state(GameState::Upgrade)
+							.and_then(not(resource_exists::<SkillOffers>)),
+					),
+					(select_skill, offer_action, update_lvlup_menu).run_if(
+						in_state(GameState::Upgrade).and_then(resource_exists::<SkillOffers>),
+					),
+				),
+			)
+			.add_systems(OnExit(GameState::Upgrade), despawn_lvlup_menu)
+			.add_systems(OnExit(GameState::Gameover), clear_level_ups);
 	}
 }
 
-fn despawn_lvlup_menu() {}
+fn queue_level_ups(
+	mut event: EventReader<PlayerLeveledUp>,
+	mut pending: ResMut<PendingLevelUps>,
+	mut game_state: ResMut<NextState<GameState>>,
+) {
+	pending.0 += event.read().count() as u32;
+	if pending.0 > 0 {
+		game_state.set(GameState::Upgrade);
+	}
+}
 
-fn get_text_element(ka: &Killable, fa: Res<FontAssets>) -> Text {
-	let style = TextStyle {
-		font: fa.fira_sans.clone(),
-		font_size: 15.0,
-		color: Color::ANTIQUE_WHITE,
-	};
-	let current = ka.hp;
-	let max = ka.hp_max;
-	Text::from_section(format!("{current}/{max}"), style).with_justify(JustifyText::Right)
+fn clear_level_ups(mut pending: ResMut<PendingLevelUps>) {
+	pending.0 = 0;
 }
 
-fn update_lvlup_menu(
-	query: Query<&Killable, With<Player>>,
-	f_assets: Res<FontAssets>,
-	mut hb_query: Query<(&mut Text, &HealthBarComponent), With<HealthBarComponent>>,
+fn despawn_lvlup_menu(mut commands: Commands, query: Query<Entity, With<LevelupMenuRoot>>) {
+	for entity in &query {
+		commands.entity(entity).despawn_recursive();
+	}
+	commands.remove_resource::<SkillOffers>();
+}
+
+/// Cards on screen and what it takes to close them
+#[derive(SystemParam)]
+struct OfferMenu<'w, 's> {
+	commands: Commands<'w, 's>,
+	root_query: Query<'w, 's, Entity, With<LevelupMenuRoot>>,
+}
+
+impl OfferMenu<'_, '_> {
+	/// Drop the cards, the spawn system draws a new set while still in upgrade state
+	fn close(&mut self) {
+		for entity in &self.root_query {
+			self.commands.entity(entity).despawn_recursive();
+		}
+		self.commands.remove_resource::<SkillOffers>();
+	}
+}
+
+fn apply_offer(offer: &Offer, skills: &mut SkillManager, stats: &mut PlayerStats) {
+	match offer {
+		Offer::Skill(skill) => {
+			if skills.level_up(skill) {
+				let (stat, modifier) = skill.modifier();
+				stats.add_modifier(stat, modifier);
+			}
+		}
+		Offer::Evolution(evolution) => {
+			if skills.evolve(*evolution) {
+				let source = ModifierSource::Evolution(*evolution);
+				for (stat, kind, value) in evolution.modifiers() {
+					stats.add_modifier(stat, Modifier { kind, source, value });
+				}
+			}
+		}
+	}
+}
+
+fn select_skill(
+	mut menu: OfferMenu,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	interaction_query: Query<(&Interaction, &SkillCard), Changed<Interaction>>,
+	mut offers: ResMut<SkillOffers>,
+	mut skills: ResMut<SkillManager>,
+	mut query: Query<&mut PlayerStats, With<Player>>,
+	mut pending: ResMut<PendingLevelUps>,
 ) {
-	if let Ok((mut text, hp_b)) = hb_query.get_single_mut() {
-		if let Ok(killable) = query.get_single() {
-			if hp_b.hp != killable.hp || hp_b.hp_max != killable.hp {
-				*text = get_text_element(killable, f_assets);
+	let count = offers.cards.len();
+	let mut chosen = None;
+	for (interaction, card) in &interaction_query {
+		match *interaction {
+			Interaction::Pressed => chosen = Some(card.0),
+			Interaction::Hovered => offers.selected = card.0,
+			Interaction::None => {}
+		}
+	}
+	if keyboard_input.any_just_pressed([KeyCode::ArrowLeft, KeyCode::KeyA]) {
+		offers.selected = (offers.selected + count - 1) % count;
+	}
+	if keyboard_input.any_just_pressed([KeyCode::ArrowRight, KeyCode::KeyD]) {
+		offers.selected = (offers.selected + 1) % count;
+	}
+	if keyboard_input.any_just_pressed([KeyCode::Enter, KeyCode::Space]) {
+		chosen = Some(offers.selected);
+	}
+	for (index, key) in CARD_KEYS.iter().enumerate().take(count) {
+		if keyboard_input.just_pressed(*key) {
+			chosen = Some(index);
+		}
+	}
+
+	if let Some(index) = chosen {
+		if let Ok(mut stats) = query.get_single_mut() {
+			apply_offer(&offers.cards[index], &mut skills, &mut stats);
+		}
+		menu.close();
+		// The spawn system goes back to playing once no level-up is pending
+		pending.0 = pending.0.saturating_sub(1);
+	}
+}
+
+fn offer_action(
+	mut menu: OfferMenu,
+	keyboard_input: Res<ButtonInput<KeyCode>>,
+	interaction_query: Query<(&Interaction, &OfferAction), Changed<Interaction>>,
+	offers: Res<SkillOffers>,
+	mut skills: ResMut<SkillManager>,
+	mut charges: ResMut<SkillCharges>,
+) {
+	let mut action = None;
+	for (interaction, offer_action) in &interaction_query {
+		if *interaction == Interaction::Pressed {
+			action = Some(*offer_action);
+		}
+	}
+	if keyboard_input.just_pressed(KeyCode::KeyR) {
+		action = Some(OfferAction::Reroll);
+	}
+	if keyboard_input.just_pressed(KeyCode::KeyX) {
+		action = Some(OfferAction::Banish);
+	}
+
+	match action {
+		Some(OfferAction::Reroll) if charges.rerolls > 0 => {
+			charges.rerolls -= 1;
+			menu.close();
+		}
+		Some(OfferAction::Banish) if charges.banishes > 0 => {
+			// Evolutions can not be banished
+			if let Offer::Skill(skill) = &offers.cards[offers.selected] {
+				charges.banishes -= 1;
+				skills.banish(skill.mode);
+				menu.close();
 			}
 		}
+		_ => {}
+	}
+}
+
+fn update_lvlup_menu(
+	offers: Res<SkillOffers>,
+	mut query: Query<(&SkillCard, &mut BackgroundColor)>,
+) {
+	for (card, mut color) in query.iter_mut() {
+		let card_color = if card.0 == offers.selected {
+			CARD_SELECTED
+		} else {
+			CARD_NORMAL
+		};
+		*color = card_color.into();
 	}
 }
 
@@ -59,63 +215,160 @@ fn spawn_lvlup_menu(
 	mut commands: Commands,
 	f_assets: Res<FontAssets>,
 	t_assets: Res<TextureAssets>,
-	query: Query<&Killable, With<Player>>,
-	hb_query: Query<&HealthBarComponent>,
+	skills: Res<SkillManager>,
+	charges: Res<SkillCharges>,
+	mut pending: ResMut<PendingLevelUps>,
+	mut game_state: ResMut<NextState<GameState>>,
 ) {
-	if hb_query.iter().count() > 0 {
+	let offers = skills.get_skills();
+	if offers.is_empty() || pending.0 == 0 {
+		pending.0 = 0;
+		game_state.set(GameState::Playing);
 		return;
 	}
-	if let Ok(killable) = query.get_single() {
-		let text = get_text_element(killable, f_assets);
-		commands
-			.spawn(NodeBundle {
-				style: Style {
-					position_type: PositionType::Absolute,
-					width: Val::Percent(100.0),
-					height: Val::Percent(100.0),
-					align_items: AlignItems::Start,
-					justify_content: JustifyContent::End,
+	let title_style = TextStyle {
+		font: f_assets.fira_sans.clone(),
+		font_size: 40.0,
+		color: Color::rgb(0.9, 0.9, 0.9),
+	};
+	let name_style = TextStyle {
+		font: f_assets.fira_sans.clone(),
+		font_size: 20.0,
+		color: Color::rgb(0.9, 0.9, 0.9),
+	};
+	let description_style = TextStyle {
+		font: f_assets.fira_sans.clone(),
+		font_size: 14.0,
+		color: Color::ANTIQUE_WHITE,
+	};
+	commands
+		.spawn(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				align_items: AlignItems::Center,
+				justify_content: JustifyContent::Center,
+				..default()
+			},
+			background_color: Color::rgba(0.0, 0.0, 0.0, 0.4).into(),
+			..default()
+		})
+		.insert(LevelupMenuRoot)
+		.with_children(|parent| {
+			parent.spawn(TextBundle::from_section("Level up!", title_style).with_style(
+				Style {
+					margin: UiRect::bottom(Val::Px(20.)),
 					..default()
 				},
-				..default()
-			})
-			.with_children(|parent| {
-				parent
-					.spawn(HealthBarBundle {
-						text: TextBundle { text, ..default() }.with_style(Style {
-							margin: UiRect::new(
-								Val::Px(0.),
-								Val::Px(0.),
-								Val::Px(15.),
-								Val::Px(0.),
-							),
-							..default()
-						}),
-					})
-					.insert(HealthBarComponent {
-						hp: killable.hp,
-						hp_max: killable.hp_max,
-					});
-				parent.spawn(ImageBundle {
-					image: UiImage {
-						texture: t_assets.heart.clone(),
-						..Default::default()
-					},
+			));
+			parent
+				.spawn(NodeBundle {
 					style: Style {
-						margin: UiRect::new(
-							Val::Px(0.),
-							Val::Px(0.),
-							Val::Px(-10.),
-							Val::Px(0.),
-						),
+						flex_direction: FlexDirection::Row,
+						column_gap: Val::Px(15.),
 						..default()
 					},
-					transform: Transform {
-						scale: Vec3::new(0.5, 0.5, 1.),
-						..Default::default()
+					..default()
+				})
+				.with_children(|parent| {
+					for (index, offer) in offers.iter().enumerate() {
+						parent
+							.spawn((
+								ButtonBundle {
+									style: Style {
+										width: Val::Px(200.0),
+										height: Val::Px(220.0),
+										padding: UiRect::all(Val::Px(10.)),
+										border: UiRect::all(Val::Px(3.)),
+										flex_direction: FlexDirection::Column,
+										align_items: AlignItems::Center,
+										justify_content: JustifyContent::SpaceAround,
+										..default()
+									},
+									background_color: CARD_NORMAL.into(),
+									border_color: offer.color().into(),
+									..default()
+								},
+								SkillCard(index),
+							))
+							.with_children(|parent| {
+								parent.spawn(TextBundle::from_section(
+									format!("[{}] {}", index + 1, offer.label()),
+									TextStyle {
+										color: offer.color(),
+										..description_style.clone()
+									},
+								));
+								parent.spawn(ImageBundle {
+									image: UiImage {
+										texture: offer.icon(&t_assets),
+										..default()
+									},
+									style: Style {
+										width: Val::Px(64.),
+										height: Val::Px(64.),
+										..default()
+									},
+									..default()
+								});
+								parent.spawn(TextBundle::from_section(
+									offer.name(),
+									name_style.clone(),
+								));
+								parent.spawn(
+									TextBundle::from_section(
+										offer.description(),
+										description_style.clone(),
+									)
+									.with_text_justify(JustifyText::Center),
+								);
+							});
+					}
+				});
+			parent
+				.spawn(NodeBundle {
+					style: Style {
+						flex_direction: FlexDirection::Row,
+						column_gap: Val::Px(15.),
+						margin: UiRect::top(Val::Px(20.)),
+						..default()
 					},
-					..Default::default()
+					..default()
+				})
+				.with_children(|parent| {
+					let actions = [
+						(OfferAction::Reroll, format!("[R] Reroll ({})", charges.rerolls)),
+						(OfferAction::Banish, format!("[X] Banish ({})", charges.banishes)),
+					];
+					for (action, label) in actions {
+						parent
+							.spawn((
+								ButtonBundle {
+									style: Style {
+										width: Val::Px(140.0),
+										height: Val::Px(35.0),
+										justify_content: JustifyContent::Center,
+										align_items: AlignItems::Center,
+										..default()
+									},
+									background_color: CARD_NORMAL.into(),
+									..default()
+								},
+								action,
+							))
+							.with_children(|parent| {
+								parent.spawn(TextBundle::from_section(
+									label,
+									description_style.clone(),
+								));
+							});
+					}
 				});
-			});
-	}
+		});
+	commands.insert_resource(SkillOffers {
+		cards: offers,
+		selected: 0,
+	});
 }
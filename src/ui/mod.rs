@@ -1,4 +1,7 @@
+pub mod charge;
 pub mod damage;
 pub mod exp;
+pub mod graze;
 pub mod health;
 pub mod levelup;
+pub mod shop;
@@ -0,0 +1,173 @@
+use bevy::prelude::*;
+
+use crate::{
+	loading::FontAssets,
+	menu::ButtonColors,
+	meta::{MetaProgress, MetaUpgrade},
+	GameState,
+};
+
+pub struct ShopPlugin;
+
+#[derive(Component)]
+struct ShopRoot;
+
+#[derive(Component)]
+struct CurrencyText;
+
+#[derive(Component)]
+struct UpgradeText(MetaUpgrade);
+
+/// Buttons whose interaction changed this frame
+type ChangedButton = (Changed<Interaction>, With<Button>);
+
+#[derive(Component)]
+enum ShopButtonAction {
+	Buy(MetaUpgrade),
+	Back,
+}
+
+impl Plugin for ShopPlugin {
+	fn build(&self, app: &mut App) {
+		app.add_systems(OnEnter(GameState::Shop), setup_shop)
+			.add_systems(
+				Update,
+				(
+					shop_action.run_if(in_state(GameState::Shop)),
+					update_shop_text.run_if(in_state(GameState::Shop)),
+				),
+			)
+			.add_systems(OnExit(GameState::Shop), cleanup_shop);
+	}
+}
+
+fn get_upgrade_text(meta: &MetaProgress, upgrade: MetaUpgrade) -> String {
+	let level = meta.level(upgrade);
+	let price = if meta.is_maxed(upgrade) {
+		"MAX".to_string()
+	} else {
+		format!("{} coins", upgrade.cost(level))
+	};
+	format!("{} Lv {level}  ({})  {price}", upgrade.name(), upgrade.description())
+}
+
+fn setup_shop(
+	mut commands: Commands,
+	font_assets: Res<FontAssets>,
+	button_colors: Res<ButtonColors>,
+	meta: Res<MetaProgress>,
+) {
+	let text_style = TextStyle {
+		font: font_assets.fira_sans.clone(),
+		font_size: 20.0,
+		color: Color::rgb(0.9, 0.9, 0.9),
+	};
+	let button_style = Style {
+		width: Val::Px(420.0),
+		height: Val::Px(40.0),
+		margin: UiRect::all(Val::Px(4.)),
+		justify_content: JustifyContent::Center,
+		align_items: AlignItems::Center,
+		..Default::default()
+	};
+	commands
+		.spawn(NodeBundle {
+			style: Style {
+				position_type: PositionType::Absolute,
+				width: Val::Percent(100.0),
+				height: Val::Percent(100.0),
+				flex_direction: FlexDirection::Column,
+				align_items: AlignItems::Center,
+				justify_content: JustifyContent::Center,
+				..default()
+			},
+			background_color: button_colors.fade.into(),
+			..default()
+		})
+		.insert(ShopRoot)
+		.with_children(|parent| {
+			parent
+				.spawn(TextBundle::from_section(
+					format!("Coins: {}", meta.currency),
+					TextStyle {
+						font_size: 40.0,
+						..text_style.clone()
+					},
+				))
+				.insert(CurrencyText);
+			for upgrade in MetaUpgrade::ALL {
+				parent
+					.spawn((
+						ButtonBundle {
+							style: button_style.clone(),
+							background_color: button_colors.normal.into(),
+							..Default::default()
+						},
+						ShopButtonAction::Buy(upgrade),
+					))
+					.with_children(|parent| {
+						parent
+							.spawn(TextBundle::from_section(
+								get_upgrade_text(&meta, upgrade),
+								text_style.clone(),
+							))
+							.insert(UpgradeText(upgrade));
+					});
+			}
+			parent
+				.spawn((
+					ButtonBundle {
+						style: button_style.clone(),
+						background_color: button_colors.normal.into(),
+						..Default::default()
+					},
+					ShopButtonAction::Back,
+				))
+				.with_children(|parent| {
+					parent.spawn(TextBundle::from_section("Back", text_style.clone()));
+				});
+		});
+}
+
+fn shop_action(
+	interaction_query: Query<(&Interaction, &ShopButtonAction), ChangedButton>,
+	mut meta: ResMut<MetaProgress>,
+	mut game_state: ResMut<NextState<GameState>>,
+) {
+	for (interaction, action) in &interaction_query {
+		if *interaction == Interaction::Pressed {
+			match action {
+				ShopButtonAction::Buy(upgrade) => {
+					if meta.buy(*upgrade) {
+						meta.save();
+					}
+				}
+				ShopButtonAction::Back => {
+					game_state.set(GameState::Menu);
+				}
+			}
+		}
+	}
+}
+
+fn update_shop_text(
+	meta: Res<MetaProgress>,
+	mut currency_query: Query<&mut Text, (With<CurrencyText>, Without<UpgradeText>)>,
+	mut upgrade_query: Query<(&mut Text, &UpgradeText)>,
+) {
+	if !meta.is_changed() {
+		return;
+	}
+	for mut text in currency_query.iter_mut() {
+		text.sections[0].value = format!("Coins: {}", meta.currency);
+	}
+	for (mut text, upgrade) in upgrade_query.iter_mut() {
+		text.sections[0].value = get_upgrade_text(&meta, upgrade.0);
+	}
+}
+
+fn cleanup_shop(mut commands: Commands, query: Query<Entity, With<ShopRoot>>) {
+	for entity in &query {
+		commands.entity(entity).despawn_recursive();
+	}
+}